@@ -0,0 +1,39 @@
+use tokio::net::TcpListener;
+use zelus::error::BlankError;
+use zelus::router::ZelusRouter;
+use zelus::{axum, service};
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/items", method = GET, no_auth)]
+    async fn example1(&self) -> Result<Vec<u32>, BlankError> {
+        Ok(vec![1, 2, 3])
+    }
+
+    #[route("/items", method = POST, no_auth)]
+    async fn example2(&self, item: u32) -> Result<u32, BlankError> {
+        Ok(item)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    axum::serve(
+        TcpListener::bind("[::]:3000")
+            .await
+            .expect("Unable to bind"),
+        ZelusRouter::new()
+            .nest("/api", Base.routes_default())
+            // `OPTIONS /api/items` answers with `Allow: GET,POST,OPTIONS,HEAD`, `true` also documents HEAD and OPTIONS
+            .automatic_methods(true)
+            .into_openapi()
+            .split_for_parts()
+            .0
+            .into_make_service(),
+    )
+    .await
+    .expect("Unable to serve http server");
+}
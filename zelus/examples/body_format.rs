@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use zelus::codec::BodyFormat;
+use zelus::error::BlankError;
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{reqwest, service, utoipa};
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+struct ExampleResult {
+    test: u32,
+}
+
+// Payloads are decoded according to their Content-Type and responses are encoded according to the Accept header.
+// JSON is always available, MessagePack and CBOR are enabled with the `msgpack` and `cbor` features.
+#[service]
+trait ExampleService {
+    #[route("/", method = POST, no_auth)]
+    async fn example(&self, test: u32) -> Result<ExampleResult, BlankError>;
+}
+
+struct Client {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ZelusClientImpl for Client {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The service has no routes answering with redirects
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The format used for payloads and requested for responses, JSON if not overridden
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::ALL.last().copied().unwrap_or_default()
+    }
+}
+
+impl ExampleServiceClientImpl for Client {}
+
+fn main() {}
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::net::TcpListener;
+use zelus::axum::response::Response;
+use zelus::error::BlankError;
+use zelus::http::header::CONTENT_ENCODING;
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{axum, reqwest, service};
+
+static COMPRESSED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone)]
+struct Base;
+
+// Negotiates gzip, br and zstd depending on the enabled features, like `ZelusRouter::compression`
+#[service(compression)]
+impl ExampleService for Base {
+    #[route("/", method = GET, no_auth)]
+    async fn example(&self) -> Result<Vec<u32>, BlankError> {
+        Ok((0..10_000).collect())
+    }
+}
+
+struct Client {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ZelusClientImpl for Client {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The service has no routes answering with redirects
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl ExampleServiceClientImpl for Client {}
+
+async fn check_encoding(response: Response) -> Response {
+    if response.headers().contains_key(CONTENT_ENCODING) {
+        COMPRESSED.store(true, Ordering::Relaxed);
+    }
+    response
+}
+
+#[tokio::main]
+async fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Unable to bind");
+    let base_url = Url::parse(&format!(
+        "http://{}/",
+        listener.local_addr().expect("Unable to get the address")
+    ))
+    .expect("Invalid url");
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            Base.routes_default()
+                .layer(axum::middleware::map_response(check_encoding)) // Sees the compressed responses
+                .into_openapi()
+                .split_for_parts()
+                .0
+                .into_make_service(),
+        )
+        .await
+        .expect("Unable to serve http server");
+    });
+
+    // The same features send `Accept-Encoding` from the SDK and decode the responses
+    let client = Client {
+        base_url,
+        client: reqwest::Client::new(),
+    };
+    let values = client.example().await.expect("Request failed");
+    assert_eq!(values.len(), 10_000);
+    assert!(
+        COMPRESSED.load(Ordering::Relaxed),
+        "Response wasn't compressed"
+    );
+}
@@ -0,0 +1,53 @@
+use axum_extra::TypedHeader;
+use axum_extra::headers::IfMatch;
+use core::time::Duration;
+use tokio::net::TcpListener;
+use zelus::cors::CorsPolicy;
+use zelus::error::BlankError;
+use zelus::{axum, define_header_variable, define_path_variable, service};
+
+define_path_variable!(id "The id of the item");
+define_header_variable!(if_match "Only update if the item is unchanged");
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/items", method = GET, no_auth)]
+    async fn example1(&self) -> Result<Vec<u32>, BlankError> {
+        Ok(vec![1, 2, 3])
+    }
+
+    #[route("/items/{id}", method = PUT)] // Allows `Authorization` because of the missing "no_auth", `Content-Type` for the payload and `If-Match`
+    async fn example2(
+        &self,
+        id: u32,
+        if_match: TypedHeader<IfMatch>,
+        item: u32,
+    ) -> Result<u32, BlankError> {
+        drop(if_match); // Compare with the current version of the item here
+        Ok(id + item)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    axum::serve(
+        TcpListener::bind("[::]:3000")
+            .await
+            .expect("Unable to bind"),
+        Base.routes_default()
+            .cors(
+                CorsPolicy::origins_with_credentials(["https://example.com"])
+                    .expect("Invalid origin")
+                    .max_age(Duration::from_hours(1)),
+            )
+            .into_openapi()
+            .split_for_parts()
+            .0
+            .into_make_service(),
+    )
+    .await
+    .expect("Unable to serve http server");
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+use zelus::error::{ZelusErrorSource, define_error};
+use zelus::{define_error_source, service};
+
+#[derive(Debug, thiserror::Error)]
+enum PaymentError {
+    #[error("The card was declined")]
+    Declined,
+    #[error("The payment provider is unavailable")]
+    Unavailable,
+    #[error("The payment provider failed: {0}")]
+    Other(String),
+}
+
+impl ZelusErrorSource for PaymentError {
+    const KEYS: &'static [&'static str] = &["declined", "unavailable"];
+
+    fn classify(&self) -> Option<&'static str> {
+        match self {
+            Self::Declined => Some("declined"),
+            Self::Unavailable => Some("unavailable"),
+            Self::Other(_msg) => None,
+        }
+    }
+}
+
+// Makes `[payment:...]` available in error attributes
+define_error_source!(payment PaymentError);
+
+define_error!(payment {
+    failed ("The payment failed" INTERNAL_SERVER_ERROR),
+    declined ("The payment was declined" PAYMENT_REQUIRED),
+    unavailable ("The payment provider is unavailable" SERVICE_UNAVAILABLE),
+});
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/checkout", method = POST, no_auth)]
+    // Entries which aren't in `PaymentError::KEYS` fail to compile
+    #[error(payment(failed [payment:*] declined [payment:declined] unavailable [payment:unavailable]))]
+    async fn checkout(&self) -> Result<(), _> {
+        Err(PaymentError::Declined.into()) // Converted into `CheckoutError::PaymentDeclined`
+    }
+}
+
+fn main() {}
@@ -1,7 +1,8 @@
+use core::time::Duration;
 use std::io;
 use std::io::ErrorKind;
 use zelus::error::define_error;
-use zelus::service;
+use zelus::{reqwest, service, tokio};
 
 #[derive(Clone)]
 #[expect(dead_code)]
@@ -10,6 +11,11 @@ struct Base;
 define_error!(category {
     error1 ("This is the error message for error1" IM_A_TEAPOT),
     error2 ("This is the error message for error2" IM_A_TEAPOT),
+    invalid_json ("The stored document is invalid" INTERNAL_SERVER_ERROR),
+    truncated_json ("The stored document is truncated" INTERNAL_SERVER_ERROR),
+    timeout ("The operation timed out" GATEWAY_TIMEOUT),
+    upstream ("The upstream service failed" BAD_GATEWAY),
+    upstream_not_found ("The upstream resource was not found" NOT_FOUND),
 });
 
 #[service]
@@ -23,6 +29,30 @@ impl ExampleService for Base {
             Err(io::Error::other("io error").into()) // This will be converted into a ExampleError::Error1
         }
     }
+
+    #[route("/document", method = GET, no_auth)]
+    #[error(category(invalid_json [json:*] truncated_json [json:eof]))] // Entries are `io`, `syntax`, `data` and `eof`
+    async fn document(&self) -> Result<u32, _> {
+        Ok(zelus::serde_json::from_str("12")?)
+    }
+
+    #[route("/slow", method = GET, no_auth)]
+    #[error(category(timeout [timeout:*]))]
+    async fn slow(&self) -> Result<(), _> {
+        Ok(tokio::time::timeout(Duration::from_secs(1), async {}).await?)
+    }
+
+    // Maps the status of failed requests to other services, requests which failed without a status use the default.
+    // Errors of generated SDKs are converted the same way, see `upstream_errors.rs`
+    #[route("/upstream", method = GET, no_auth)]
+    #[error(category(upstream [upstream:*] upstream_not_found [upstream:404]))]
+    async fn upstream(&self) -> Result<String, _> {
+        Ok(reqwest::get("http://localhost:3001/")
+            .await?
+            .error_for_status()?
+            .text()
+            .await?)
+    }
 }
 
 fn main() {}
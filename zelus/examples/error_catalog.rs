@@ -0,0 +1,40 @@
+use zelus::error::catalog::ErrorCatalog;
+use zelus::error::define_error;
+use zelus::service;
+
+define_error!(item {
+    not_found ("The item was not found" NOT_FOUND),
+    locked ("The item is locked" LOCKED),
+});
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/items", method = GET, no_auth)]
+    #[error(item(not_found))]
+    async fn list(&self) -> Result<Vec<u32>, _> {
+        Ok(Vec::new())
+    }
+
+    #[route("/items", method = DELETE, no_auth)]
+    #[error(item(not_found locked) { Forbidden "Deleting items is forbidden" FORBIDDEN })]
+    async fn delete(&self) -> Result<(), _> {
+        Ok(())
+    }
+}
+
+fn main() {
+    let openapi = Base
+        .routes_default()
+        .error_catalog() // Adds the `x-error-catalog` extension, listing every error with its operations
+        .into_openapi()
+        .split_for_parts()
+        .1;
+
+    // The same catalog as library call, e.g. to generate an error reference
+    let catalog = ErrorCatalog::from_openapi(&openapi);
+    println!("{}", catalog.to_markdown());
+    println!("{:#}", catalog.to_json());
+}
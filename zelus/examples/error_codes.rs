@@ -0,0 +1,35 @@
+use zelus::error::{ErrorCause, define_error};
+use zelus::{define_path_variable, service};
+
+define_path_variable!(id "The id of the item");
+
+define_error!(item {
+    /// The item does not exist or was deleted.
+    ///
+    /// Items are deleted after **30 days** in the trash.
+    not_found ("The item was not found" NOT_FOUND code = 1001 help_url = "https://example.com/errors/1001"),
+    // Both are optional, `{ .. }` details are placed before or after them
+    locked ("The item is locked" LOCKED { until: u64 } code = 1002),
+});
+
+#[derive(Clone)]
+#[expect(dead_code)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    // The code and help link are sent as `error.code` and `error.help_url`, and documented
+    #[route("/items/{id}", method = GET, no_auth)]
+    #[error(item(not_found locked { .. }))]
+    async fn example(&self, id: u32) -> Result<u32, _> {
+        if id == 0 {
+            Err(example_service_error::ExampleError::ItemNotFound(
+                ErrorCause::none(),
+            ))
+        } else {
+            Ok(id)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+use tokio::net::TcpListener;
+use zelus::error::{ErrorCause, define_error, error};
+use zelus::{axum, convert_errors, service};
+
+define_error!(category {
+    error1 ("This is the error message for error1" NOT_FOUND),
+    error2 ("This is the error message for error2" CONFLICT),
+});
+
+error!(StandaloneError, category(error1 [io:*]));
+
+// Converted into the errors of the routes with `?`
+convert_errors!(StandaloneError => example_service_error::Example1Error, example_service_error::Example2Error);
+convert_errors!(try example_service_error::Example2Error => StandaloneError);
+
+#[derive(Clone)]
+struct Base;
+
+fn shared() -> Result<String, StandaloneError> {
+    Ok(std::fs::read_to_string("config.toml")?)
+}
+
+// The errors of the routes are converted into each other
+#[service(convert_errors)]
+impl ExampleService for Base {
+    #[route("/1", method = GET, no_auth)]
+    #[error(category(error1 error2))]
+    async fn example1(&self) -> Result<(), _> {
+        shared()?;
+        self.example2().await?;
+        Ok(())
+    }
+
+    #[route("/2", method = GET, no_auth)]
+    #[error(category(error2))]
+    async fn example2(&self) -> Result<(), _> {
+        // `category/error1` is missing in this error, it becomes `Foreign` and is rendered as is
+        shared()?;
+        Err(example_service_error::Example2Error::CategoryError2(
+            ErrorCause::none(),
+        ))
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    axum::serve(
+        TcpListener::bind("[::]:3000")
+            .await
+            .expect("Unable to bind"),
+        Base.routes_default()
+            .into_openapi()
+            .split_for_parts()
+            .0
+            .into_make_service(),
+    )
+    .await
+    .expect("Unable to serve http server");
+}
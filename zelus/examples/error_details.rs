@@ -0,0 +1,32 @@
+use zelus::error::{ErrorCause, define_error};
+use zelus::{define_path_variable, service};
+
+define_path_variable!(id "The id of the item");
+
+define_error!(item {
+    not_found ("The item was not found" NOT_FOUND { id: u32 }), // The fields are serialized under `error.details`
+    quota_exceeded ("Too many items" TOO_MANY_REQUESTS { limit: u64 }),
+    locked ("The item is locked" LOCKED),
+});
+
+#[derive(Clone)]
+#[expect(dead_code)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/items/{id}", method = GET, no_auth)]
+    #[error(item(not_found { .. } quota_exceeded { .. } locked))] // `{ .. }` marks errors with details
+    async fn example(&self, id: u32) -> Result<u32, _> {
+        if id == 0 {
+            Err(example_service_error::ExampleError::ItemNotFound(
+                error_item_not_found::ItemNotFoundDetails { id },
+                ErrorCause::none(),
+            ))
+        } else {
+            Ok(id)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+use tokio::net::TcpListener;
+use zelus::error::define_error;
+use zelus::{axum, define_path_variable, service};
+
+define_error!(category {
+    error1 ("This is the error message for error1" INTERNAL_SERVER_ERROR),
+});
+
+define_path_variable!(id "The id of the entry");
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/{id}", method = GET, no_auth)]
+    #[error(category(error1 [io:*]))]
+    async fn example(&self, id: u32) -> Result<(), _> {
+        Err(std::io::Error::other(format!("unable to read {id}")).into())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    axum::serve(
+        TcpListener::bind("[::]:3000")
+            .await
+            .expect("Unable to bind"),
+        Base.routes_default()
+            // Called for every error response of the routes above, `on_error_with_request_id` reads
+            // the request id from another header than `x-request-id`
+            .on_error(|ctx| {
+                eprintln!(
+                    "{} {} ({:?}, request {:?}) failed with {} ({}): {:?}",
+                    ctx.method,
+                    ctx.path.unwrap_or_default(),
+                    ctx.operation_id,
+                    ctx.request_id,
+                    ctx.id,
+                    ctx.status,
+                    ctx.source(),
+                );
+            })
+            .into_openapi()
+            .split_for_parts()
+            .0
+            .into_make_service(),
+    )
+    .await
+    .expect("Unable to serve http server");
+}
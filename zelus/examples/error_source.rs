@@ -0,0 +1,50 @@
+use tokio::net::TcpListener;
+use zelus::error::{ErrorCause, define_error};
+use zelus::{axum, service};
+
+define_error!(category {
+    error1 ("This is the error message for error1" INTERNAL_SERVER_ERROR),
+    error2 ("This is the error message for error2" NOT_FOUND),
+});
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/", method = GET, no_auth)]
+    #[error(category(error1 [io:*] error2))]
+    async fn example(&self) -> Result<(), _> {
+        if false {
+            // `?` keeps what the error was converted from as its cause
+            std::fs::read("config.toml")?;
+            Ok(())
+        } else {
+            // Any other cause can be attached, the response body stays the same
+            Err(
+                example_service_error::ExampleError::CategoryError2(ErrorCause::none())
+                    .with_source("missing row"),
+            )
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    axum::serve(
+        TcpListener::bind("[::]:3000")
+            .await
+            .expect("Unable to bind"),
+        Base.routes_default()
+            .on_error(|ctx| {
+                // `ctx.error` displays the message of the error, its source is the kept cause
+                eprintln!("{} ({}): {:?}", ctx.id, ctx.status, ctx.source());
+            })
+            .into_openapi()
+            .split_for_parts()
+            .0
+            .into_make_service(),
+    )
+    .await
+    .expect("Unable to serve http server");
+}
@@ -0,0 +1,47 @@
+use tokio::net::TcpListener;
+use zelus::error::{ErrorCause, define_error};
+use zelus::locale::MessageCatalog;
+use zelus::{axum, service};
+
+define_error!(category {
+    error1 ("This is the error message for error1" IM_A_TEAPOT), // The default locale, listed in the documentation
+});
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/", method = GET, no_auth)]
+    #[error(category(error1))]
+    async fn example(&self) -> Result<(), _> {
+        Err(example_service_error::ExampleError::CategoryError1(
+            ErrorCause::none(),
+        ))
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let catalog = MessageCatalog::new()
+        .locale_json("de", include_str!("locales/de.json")) // Messages by error id, the id itself stays the same
+        .expect("Invalid message catalog")
+        .locale(
+            "fr",
+            [("category/error1", "Ceci est le message d'erreur de error1")],
+        );
+
+    axum::serve(
+        TcpListener::bind("[::]:3000")
+            .await
+            .expect("Unable to bind"),
+        Base.routes_default()
+            .localize(catalog) // `Accept-Language: de-DE, en;q=0.8` answers with the german message
+            .into_openapi()
+            .split_for_parts()
+            .0
+            .into_make_service(),
+    )
+    .await
+    .expect("Unable to serve http server");
+}
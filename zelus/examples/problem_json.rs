@@ -0,0 +1,100 @@
+use tokio::net::TcpListener;
+use zelus::error::format::ProblemJson;
+use zelus::error::{ErrorCause, define_error};
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{axum, define_path_variable, reqwest, service};
+
+define_path_variable!(id "The id of the item");
+
+define_error!(item {
+    not_found ("The item was not found" NOT_FOUND { id: u32 }),
+    locked ("The item is locked" LOCKED),
+});
+
+#[derive(Clone)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    #[route("/items/{id}", method = GET, no_auth)]
+    #[error(item(not_found { .. } locked))]
+    async fn example(&self, id: u32) -> Result<u32, _> {
+        if id == 0 {
+            Err(example_service_error::ExampleError::ItemNotFound(
+                error_item_not_found::ItemNotFoundDetails { id },
+                ErrorCause::none(),
+            ))
+        } else {
+            Err(example_service_error::ExampleError::ItemLocked(
+                ErrorCause::none(),
+            ))
+        }
+    }
+}
+
+struct Client {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ZelusClientImpl for Client {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The service has no routes answering with redirects
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl ExampleServiceClientImpl for Client {}
+
+#[tokio::main]
+async fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Unable to bind");
+    let base_url = Url::parse(&format!(
+        "http://{}/",
+        listener.local_addr().expect("Unable to get the address")
+    ))
+    .expect("Invalid url");
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            Base.routes_default()
+                // {"type": "https://example.com/errors/item/not_found", "title": "The item was not found",
+                //  "status": 404, "instance": "<x-request-id>", "id": "item/not_found", "details": {"id": 0}}
+                .error_format(ProblemJson::new("https://example.com/errors/"))
+                .into_openapi()
+                .split_for_parts()
+                .0
+                .into_make_service(),
+        )
+        .await
+        .expect("Unable to serve http server");
+    });
+
+    // The SDK decodes the problem details into the same errors as the default format
+    let client = Client {
+        base_url,
+        client: reqwest::Client::new(),
+    };
+    assert!(matches!(
+        client.example(0).await,
+        Err(example_service_error::ExampleError::ItemNotFound(
+            error_item_not_found::ItemNotFoundDetails { id: 0 },
+            _
+        ))
+    ));
+    assert!(matches!(
+        client.example(1).await,
+        Err(example_service_error::ExampleError::ItemLocked(_))
+    ));
+}
@@ -0,0 +1,32 @@
+use zelus::error::BlankError;
+use zelus::responses::Redirect;
+use zelus::url::Url;
+use zelus::{define_path_variable, service};
+
+define_path_variable!(id "The id of the resource");
+
+#[derive(Clone)]
+#[expect(dead_code)]
+struct Base {
+    public_url: Url,
+}
+
+#[service]
+impl ExampleService for Base {
+    #[route("/old/{id}", method = GET, no_auth)]
+    async fn example1(&self, id: u32) -> Result<Redirect<301>, BlankError> {
+        // The target is built from the url builder of the other route, so it cannot drift from the route table
+        // The SDK receives the redirect without following it: `url()` parses the target, `follow()` requests it
+        Ok(Redirect::to_url(&example_service_url::example2(
+            &self.public_url,
+            id,
+        )))
+    }
+
+    #[route("/new/{id}", method = GET, no_auth)]
+    async fn example2(&self, id: u32) -> Result<u32, BlankError> {
+        Ok(id)
+    }
+}
+
+fn main() {}
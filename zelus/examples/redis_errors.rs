@@ -0,0 +1,34 @@
+use zelus::error::define_error;
+use zelus::{redis, service};
+
+define_error!(cache {
+    failed ("The cache failed" INTERNAL_SERVER_ERROR),
+    unavailable ("The cache is unavailable" SERVICE_UNAVAILABLE),
+    busy ("The cache is busy, try again" SERVICE_UNAVAILABLE),
+    invalid_value ("The cached value has an unexpected type" INTERNAL_SERVER_ERROR),
+});
+
+#[derive(Clone)]
+#[expect(dead_code)]
+struct Base {
+    client: redis::Client,
+}
+
+#[service]
+impl ExampleService for Base {
+    #[route("/greeting", method = GET, no_auth)]
+    // Entries are checked in the order of declaration: `io`, `connection`, `timeout`, `type`,
+    // `parse`, `auth`, `busy`, `cluster` and `code("NOSCRIPT")` for any error code of the server
+    #[error(cache(
+        failed [redis:*]
+        unavailable [redis:io redis:auth]
+        busy [redis:busy]
+        invalid_value [redis:type]
+    ))]
+    async fn greeting(&self) -> Result<String, _> {
+        let mut connection = self.client.get_connection()?;
+        Ok(redis::cmd("GET").arg("greeting").query(&mut connection)?)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+use axum_extra::TypedHeader;
+use axum_extra::headers::{CacheControl, ETag, LastModified};
+use serde::{Deserialize, Serialize};
+use zelus::error::BlankError;
+use zelus::responses::{ResponseHeaders, WithHeaders};
+use zelus::{define_header_variable, service, utoipa};
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+struct ExampleResult {
+    test: u32,
+}
+
+define_header_variable!(etag "The version of the returned resource");
+define_header_variable!(last_modified "When the returned resource has been modified");
+
+#[derive(ResponseHeaders)]
+struct ExampleHeaders {
+    etag: TypedHeader<ETag>,
+    last_modified: Option<TypedHeader<LastModified>>, // Optional headers may be missing in the response
+}
+
+#[service]
+trait ExampleService {
+    #[route("/tuple", method = GET, no_auth)]
+    async fn example1(
+        &self,
+    ) -> Result<WithHeaders<ExampleResult, (TypedHeader<CacheControl>,)>, BlankError>; // Tuples of headers can be used as well
+
+    #[route("/struct", method = GET, no_auth)]
+    async fn example2(&self) -> Result<WithHeaders<ExampleResult, ExampleHeaders>, BlankError>; // The SDK returns the headers alongside the body
+}
+
+fn main() {}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+use zelus::error::BlankError;
+use zelus::responses::{Redirect, ZelusResponse};
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{reqwest, service, utoipa};
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+struct Resource {
+    test: u32,
+}
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+struct JobHandle {
+    id: u32,
+}
+
+#[derive(ZelusResponse)]
+enum ExampleResponse {
+    /// The resource is ready
+    #[status(OK)]
+    Ready(Resource),
+    /// The resource is still being computed
+    #[status(ACCEPTED)]
+    Pending(JobHandle),
+    /// The resource has a canonical url
+    #[status(SEE_OTHER)]
+    Moved(Redirect<303>), // The SDK doesn't follow redirects of routes with 3xx variants, see `redirect_client`
+    #[status(NO_CONTENT)] // Variants without a value have no body
+    Gone,
+}
+
+#[service]
+trait ExampleService {
+    #[route("/", method = GET, no_auth)]
+    async fn example(&self) -> Result<ExampleResponse, BlankError>; // The SDK picks the variant by the status code
+}
+
+struct Sdk {
+    base_url: Url,
+    client: reqwest::Client,
+    redirect_client: reqwest::Client,
+}
+
+impl Sdk {
+    fn new(base_url: Url) -> reqwest::Result<Self> {
+        // Both clients share their configuration, only the redirect policy differs
+        let builder = || reqwest::Client::builder().timeout(Duration::from_secs(10));
+        Ok(Self {
+            base_url,
+            client: builder().build()?,
+            redirect_client: builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()?,
+        })
+    }
+}
+
+impl ZelusClientImpl for Sdk {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.redirect_client
+    }
+}
+
+impl ExampleServiceClientImpl for Sdk {}
+
+fn main() {}
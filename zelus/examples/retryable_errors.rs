@@ -0,0 +1,74 @@
+use core::time::Duration;
+use zelus::error::{ErrorCause, define_error};
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{reqwest, service};
+
+define_error!(service {
+    overloaded ("The service is overloaded" SERVICE_UNAVAILABLE retry_after = 30),
+    // Computed from the details when the response is created
+    quota_exceeded ("Too many requests" TOO_MANY_REQUESTS {
+        reset_in: u64,
+    } retry_after = |details| Duration::from_secs(details.reset_in)),
+    // Retryable without telling the client how long to wait
+    conflict ("The item was changed concurrently" CONFLICT retryable),
+});
+
+#[derive(Clone)]
+#[expect(dead_code)]
+struct Base;
+
+#[service]
+impl ExampleService for Base {
+    // Errors with `retry_after` send and document the `Retry-After` header
+    #[route("/", method = GET, no_auth)]
+    #[error(service(overloaded quota_exceeded { .. } conflict))]
+    async fn example(&self) -> Result<(), _> {
+        Err(example_service_error::ExampleError::ServiceQuotaExceeded(
+            error_service_quota_exceeded::ServiceQuotaExceededDetails { reset_in: 60 },
+            ErrorCause::none(),
+        ))
+    }
+}
+
+struct Client {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ZelusClientImpl for Client {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The service has no routes answering with redirects
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl ExampleServiceClientImpl for Client {}
+
+// In the SDK, `is_retryable` tells whether a failed call may be repeated
+#[expect(dead_code)]
+async fn call(client: &Client) -> Result<(), example_service_error::ExampleError> {
+    use example_service_error::ExampleError;
+
+    loop {
+        match client.example().await {
+            // Errors unknown to this client keep the `Retry-After` of the response
+            Err(ExampleError::Unknown {
+                retry_after: Some(retry_after),
+                ..
+            }) => tokio::time::sleep(retry_after).await,
+            Err(error) if error.is_retryable() => tokio::time::sleep(Duration::from_secs(1)).await,
+            result => return result,
+        }
+    }
+}
+
+fn main() {}
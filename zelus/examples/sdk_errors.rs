@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+use zelus::error::define_error;
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{reqwest, service};
+
+define_error!(category {
+    error1 ("This is the error message for error1" IM_A_TEAPOT),
+});
+
+#[service]
+trait ExampleService {
+    #[route("/", method = GET, no_auth)]
+    #[error(category(error1))]
+    async fn example(&self) -> Result<(), _>;
+}
+
+struct Client {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ZelusClientImpl for Client {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The service has no routes answering with redirects
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl ExampleServiceClientImpl for Client {}
+
+async fn call(client: &Client) -> String {
+    use example_service_error::ExampleError;
+
+    match client.example().await {
+        Ok(()) => "Ok".to_owned(),
+        Err(ExampleError::CategoryError1(_)) => "Known error".to_owned(),
+        // The connection failed or the response could not be decoded
+        Err(ExampleError::Communication(err, _)) => format!("Communication failed: {err}"),
+        // An error added to the server after this client was built, or an error page of a proxy.
+        // Returned by a route, it answers with `502 Bad Gateway` like `Communication`
+        Err(ExampleError::Unknown {
+            status,
+            id,
+            msg,
+            body,
+            retry_after,
+            ..
+        }) => format!("Unknown error {status} {id:?} {msg:?}, retry after {retry_after:?}: {body}"),
+        Err(err) => err.to_string(),
+    }
+}
+
+fn main() {}
@@ -0,0 +1,78 @@
+use zelus::error::define_error;
+use zelus::service;
+use zelus::sqlx::SqlitePool;
+
+define_error!(user {
+    database ("The database failed" INTERNAL_SERVER_ERROR),
+    email_taken ("The email is already taken" CONFLICT),
+    handle_taken ("The handle is already taken" CONFLICT),
+    invalid_handle ("The handle is invalid" UNPROCESSABLE_ENTITY),
+    conflict ("The transaction conflicted, try again" SERVICE_UNAVAILABLE),
+});
+
+#[derive(Clone)]
+struct Base {
+    pool: SqlitePool,
+}
+
+#[service]
+impl ExampleService for Base {
+    #[route("/users", method = POST, no_auth)]
+    // Entries are checked in the order of declaration.
+    // SQLite doesn't report constraint names, with the `sqlite` feature unique constraints are named
+    // by their columns there.
+    #[error(user(
+        database [sql:*]
+        email_taken [sql:unique("users.email")]
+        handle_taken [sql:unique("users.handle")]
+        invalid_handle [sql:constraint(handle_format)]
+        conflict [sql:code("40001")]
+    ))]
+    async fn create_user(&self, email: String, handle: String) -> Result<(), _> {
+        zelus::sqlx::query("INSERT INTO users (email, handle) VALUES (?, ?)")
+            .bind(email)
+            .bind(handle)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    use example_service_error::CreateUserError;
+
+    let pool = SqlitePool::connect("sqlite::memory:")
+        .await
+        .expect("Unable to open database");
+    zelus::sqlx::query(
+        "CREATE TABLE users (
+            email TEXT NOT NULL UNIQUE,
+            handle TEXT NOT NULL UNIQUE CONSTRAINT handle_format CHECK (length(handle) > 2)
+        )",
+    )
+    .execute(&pool)
+    .await
+    .expect("Unable to create table");
+    let base = Base { pool };
+
+    base.create_user("a@example.com".to_owned(), "alice".to_owned())
+        .await
+        .expect("Unable to create user");
+    for (email, handle) in [
+        ("a@example.com", "bob"),
+        ("b@example.com", "alice"),
+        ("c@example.com", "c"),
+    ] {
+        let err = base
+            .create_user(email.to_owned(), handle.to_owned())
+            .await
+            .expect_err("Constraint not violated");
+        match &err {
+            CreateUserError::UserEmailTaken(_)
+            | CreateUserError::UserHandleTaken(_)
+            | CreateUserError::UserInvalidHandle(_) => println!("{err}"),
+            _ => panic!("Unexpected error {err:?}"),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use zelus::error::BlankError;
+use zelus::responses::{Accepted, Created, WithStatus};
+use zelus::{service, utoipa};
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+struct ExampleResult {
+    test: u32,
+}
+
+#[service]
+trait ExampleService {
+    #[route("/", method = POST, no_auth)]
+    async fn example1(&self, test: u32) -> Result<Created<ExampleResult>, BlankError>; // Answers with 201, optionally with a Location header using `Created::located`
+
+    #[route("/job", method = POST, no_auth)]
+    async fn example2(&self) -> Result<Accepted<()>, BlankError>; // Answers with 202 and no body
+
+    #[route("/other", method = GET, no_auth)]
+    async fn example3(&self) -> Result<WithStatus<206, ExampleResult>, BlankError>; // Any other 2xx status code
+
+    #[route("/plain", method = POST, no_auth, status = CREATED)]
+    async fn example4(&self, test: u32) -> Result<ExampleResult, BlankError>; // Answers with 201 without changing the result type
+}
+
+fn main() {}
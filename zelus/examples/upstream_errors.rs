@@ -0,0 +1,116 @@
+use tokio::net::TcpListener;
+use zelus::error::{ErrorCause, define_error};
+use zelus::sdk::ZelusClientImpl;
+use zelus::url::Url;
+use zelus::{axum, convert_errors, define_path_variable, reqwest, service};
+
+define_path_variable!(id "The id of the item");
+
+define_error!(item {
+    not_found ("The item was not found" NOT_FOUND),
+    locked ("The item is locked" LOCKED),
+});
+
+define_error!(gateway {
+    upstream ("The upstream service failed" BAD_GATEWAY),
+    upstream_not_found ("The upstream resource was not found" NOT_FOUND),
+});
+
+#[derive(Clone)]
+struct Items;
+
+#[service]
+impl ItemService for Items {
+    #[route("/items/{id}", method = GET, no_auth)]
+    #[error(item(not_found locked))]
+    async fn item(&self, id: u32) -> Result<u32, _> {
+        match id {
+            0 => Err(item_service_error::ItemError::ItemNotFound(
+                ErrorCause::none(),
+            )),
+            1 => Err(item_service_error::ItemError::ItemLocked(ErrorCause::none())),
+            id => Ok(id),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Client {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ZelusClientImpl for Client {
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    // The service has no routes answering with redirects
+    fn redirect_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl ItemServiceClientImpl for Client {}
+
+#[derive(Clone)]
+struct Gateway {
+    items: Client,
+}
+
+#[service]
+impl GatewayService for Gateway {
+    // The status of errors of other services picks the variant, requests which failed without a status use the default
+    #[route("/proxy/{id}", method = GET, no_auth)]
+    #[error(gateway(upstream [upstream:*] upstream_not_found [upstream:404]))]
+    async fn proxy(&self, id: u32) -> Result<u32, _> {
+        Ok(self.items.item(id).await?)
+    }
+}
+
+// `?` converts the errors of the SDK by their status
+convert_errors!(item_service_error::ItemError => gateway_service_error::ProxyError);
+
+async fn serve(router: zelus::router::ZelusRouter) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Unable to bind");
+    let base_url = Url::parse(&format!(
+        "http://{}/",
+        listener.local_addr().expect("Unable to get the address")
+    ))
+    .expect("Invalid url");
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            router
+                .into_openapi()
+                .split_for_parts()
+                .0
+                .into_make_service(),
+        )
+        .await
+        .expect("Unable to serve http server");
+    });
+    base_url
+}
+
+#[tokio::main]
+async fn main() {
+    let items = Client {
+        base_url: serve(Items.routes_default()).await,
+        client: reqwest::Client::new(),
+    };
+    let gateway = serve(Gateway { items }.routes_default()).await;
+
+    for (id, status) in [(0, 404), (1, 502), (2, 200)] {
+        let response = reqwest::get(gateway.join(&format!("proxy/{id}")).expect("Invalid url"))
+            .await
+            .expect("Request failed");
+        assert_eq!(response.status().as_u16(), status);
+    }
+}
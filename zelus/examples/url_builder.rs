@@ -0,0 +1,19 @@
+use zelus::error::BlankError;
+use zelus::url::Url;
+use zelus::{define_path_variable, define_query_variable, service};
+
+define_path_variable!(id "The id of the resource");
+define_query_variable!(page "The page to show");
+
+#[service(no_sdk)] // The url builders are generated even without the SDK
+trait ExampleService {
+    #[route("/items/{id}", method = GET, query = [ page ], no_auth)]
+    async fn example(&self, id: u32, page: Option<u32>) -> Result<(), BlankError>;
+}
+
+fn main() {
+    let base = Url::parse("https://example.com/api/").expect("Expected url");
+    // Every route has an url builder in the `<trait>_url` module, taking the base url and the path and query arguments
+    let url = example_service_url::example(&base, 5, Some(2));
+    assert_eq!(url.as_str(), "https://example.com/api/items/5?page=2");
+}
@@ -0,0 +1,128 @@
+use http::HeaderValue;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+/// Format of request and response bodies.
+///
+/// JSON is always available, other formats are enabled by their cargo feature.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BodyFormat {
+    #[default]
+    Json,
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct CodecError(pub(crate) Box<dyn core::error::Error + Send + Sync>);
+
+impl BodyFormat {
+    pub const ALL: &[Self] = &[
+        Self::Json,
+        #[cfg(feature = "msgpack")]
+        Self::MessagePack,
+        #[cfg(feature = "cbor")]
+        Self::Cbor,
+    ];
+
+    #[must_use]
+    pub const fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => "application/msgpack",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "application/cbor",
+        }
+    }
+
+    /// Parses a `Content-Type` value, ignoring its parameters.
+    #[must_use]
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let media_type = media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match media_type.as_str() {
+            "application/json" => Some(Self::Json),
+            #[cfg(feature = "msgpack")]
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Self::MessagePack)
+            }
+            #[cfg(feature = "cbor")]
+            "application/cbor" => Some(Self::Cbor),
+            media_type if media_type.ends_with("+json") => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Picks the supported format with the highest quality from an `Accept` header.
+    ///
+    /// A missing header or a wildcard results in JSON.
+    #[must_use]
+    pub fn negotiate(accept: Option<&HeaderValue>) -> Option<Self> {
+        let Some(accept) = accept.and_then(|accept| accept.to_str().ok()) else {
+            return Some(Self::Json);
+        };
+        let mut best: Option<(Self, f32)> = None;
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media_type = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality <= 0.0 {
+                continue;
+            }
+            let format = match media_type {
+                "*/*" | "application/*" => Self::Json,
+                media_type => {
+                    let Some(format) = Self::from_media_type(media_type) else {
+                        continue;
+                    };
+                    format
+                }
+            };
+            if best.is_none_or(|(_format, best_quality)| quality > best_quality) {
+                best = Some((format, quality));
+            }
+        }
+        best.map(|(format, _quality)| format)
+    }
+
+    pub fn serialize<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, CodecError> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|err| CodecError(Box::new(err))),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => {
+                rmp_serde::to_vec_named(value).map_err(|err| CodecError(Box::new(err)))
+            }
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::into_writer(value, &mut buffer)
+                    .map_err(|err| CodecError(Box::new(err)))?;
+                Ok(buffer)
+            }
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, CodecError> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes).map_err(|err| CodecError(Box::new(err))),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => {
+                rmp_serde::from_slice(bytes).map_err(|err| CodecError(Box::new(err)))
+            }
+            #[cfg(feature = "cbor")]
+            Self::Cbor => ciborium::from_reader(bytes).map_err(|err| CodecError(Box::new(err))),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use core::time::Duration;
+use http::header::InvalidHeaderValue;
+use http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_METHOD, ALLOW, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use std::sync::Arc;
+
+/// Cross-origin policy, applied with [`ZelusRouter::cors`](crate::router::ZelusRouter::cors).
+///
+/// Allowed methods and request headers are derived from the routes, so only origins,
+/// credentials and caching have to be configured. Credentials are only allowed for explicit
+/// origins, as any website could read credentialed responses otherwise.
+#[must_use]
+#[derive(Clone, Default)]
+pub struct CorsPolicy {
+    origins: Option<Vec<HeaderValue>>,
+    /// Only set with `origins`.
+    credentials: bool,
+    max_age: Option<Duration>,
+    expose_headers: Vec<HeaderName>,
+}
+
+impl CorsPolicy {
+    pub fn any_origin() -> Self {
+        Self::default()
+    }
+
+    /// Fails if an origin isn't a valid header value.
+    pub fn origins<'a>(
+        origins: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, InvalidHeaderValue> {
+        Ok(Self {
+            origins: Some(
+                origins
+                    .into_iter()
+                    .map(HeaderValue::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            ..Self::default()
+        })
+    }
+
+    /// Like [`Self::origins`], also allowing cookies and the `Authorization` header to be sent
+    /// cross-origin.
+    pub fn origins_with_credentials<'a>(
+        origins: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, InvalidHeaderValue> {
+        Ok(Self {
+            credentials: true,
+            ..Self::origins(origins)?
+        })
+    }
+
+    /// Lets browsers cache the preflight response.
+    pub const fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Response headers readable by the browser client.
+    pub fn expose_headers(mut self, headers: impl IntoIterator<Item = HeaderName>) -> Self {
+        self.expose_headers.extend(headers);
+        self
+    }
+
+    fn allow_origin(&self, origin: &HeaderValue) -> Option<HeaderValue> {
+        self.origins.as_ref().map_or_else(
+            || Some(HeaderValue::from_static("*")),
+            |origins| origins.contains(origin).then(|| origin.clone()),
+        )
+    }
+}
+
+/// Adds the headers allowing the origin of the request to read the response.
+pub(crate) async fn cors_response(
+    State(policy): State<Arc<CorsPolicy>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let allow_origin = request
+        .headers()
+        .get(ORIGIN)
+        .and_then(|origin| policy.allow_origin(origin));
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    if policy.origins.is_some() {
+        headers.append(VARY, HeaderValue::from_static("origin"));
+    }
+    if let Some(allow_origin) = allow_origin {
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        if policy.credentials {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+        if let Some(expose_headers) = join(policy.expose_headers.iter().map(HeaderName::as_str)) {
+            headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, expose_headers);
+        }
+    }
+    response
+}
+
+/// Answers `OPTIONS`, including CORS preflight requests if a policy is set.
+pub(crate) fn preflight(
+    policy: Option<&CorsPolicy>,
+    request_headers: &HeaderMap,
+    allow: HeaderValue,
+    allow_headers: Option<HeaderValue>,
+) -> Response {
+    let mut headers = HeaderMap::new();
+    let preflight = policy.filter(|policy| {
+        request_headers.contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+            && request_headers
+                .get(ORIGIN)
+                .and_then(|origin| policy.allow_origin(origin))
+                .is_some()
+    });
+    if let Some(policy) = preflight {
+        headers.insert(ACCESS_CONTROL_ALLOW_METHODS, allow.clone());
+        if let Some(allow_headers) = allow_headers {
+            headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, allow_headers);
+        }
+        if let Some(max_age) = policy.max_age {
+            headers.insert(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into());
+        }
+    }
+    headers.insert(ALLOW, allow);
+    (StatusCode::NO_CONTENT, headers).into_response()
+}
+
+pub(crate) fn join<'a>(values: impl IntoIterator<Item = &'a str>) -> Option<HeaderValue> {
+    let joined = values.into_iter().collect::<Vec<_>>().join(",");
+    (!joined.is_empty())
+        .then(|| HeaderValue::try_from(joined).expect("Names are valid header values"))
+}
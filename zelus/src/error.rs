@@ -1,18 +1,301 @@
-use http::StatusCode;
+use axum::body::{Body, HttpBody};
+use axum::response::Response;
+use bytes::Bytes;
+use core::convert::Infallible;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use format::ErrorFormat;
+use http::header::CONTENT_TYPE;
+use http::{HeaderValue, StatusCode};
+use http_body::{Frame, SizeHint};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 pub use zelus_macros::define_error;
 pub use zelus_macros::error;
 
+pub mod catalog;
+pub mod context;
+pub mod format;
+
+/// Cause kept by a generated error with `with_source`, never sent in the response.
+pub type ErrorSource = Box<dyn core::error::Error + Send + Sync>;
+
+/// The cause carried by every variant of a generated error, returned by its `Error::source`.
+///
+/// Matching on the variants works the same with or without a cause, like `Error::NotFound(_)`.
+#[derive(Debug, Default)]
+// Boxed again to keep the variants small, `ErrorSource` is a wide pointer
+pub struct ErrorCause(Option<Box<ErrorSource>>);
+
+impl ErrorCause {
+    /// No cause, for creating an error, like `Error::NotFound(ErrorCause::none())`.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self(None)
+    }
+
+    #[must_use]
+    pub fn new(source: impl Into<ErrorSource>) -> Self {
+        Self(Some(Box::new(source.into())))
+    }
+
+    #[must_use]
+    pub fn get(&self) -> Option<&(dyn core::error::Error + Send + Sync + 'static)> {
+        self.0.as_deref().map(|source| &**source)
+    }
+
+    #[must_use]
+    pub fn take(&mut self) -> Option<ErrorSource> {
+        self.0.take().map(|source| *source)
+    }
+}
+
+/// Classifies an error type for a custom error attribute, registered with
+/// [`define_error_source!`](crate::define_error_source).
+///
+/// `[name:*]` is used for errors without a key, the other entries are checked against
+/// [`Self::KEYS`] at compile time.
+pub trait ZelusErrorSource: core::error::Error + Send + Sync + 'static {
+    const KEYS: &'static [&'static str];
+
+    /// One of [`Self::KEYS`], if the error belongs to a specific entry.
+    fn classify(&self) -> Option<&'static str>;
+}
+
+/// Picks the variant an error converts to, implemented by generated errors for the errors of
+/// their attributes, like `[io:*]`.
+///
+/// `From` keeps the converted error as the cause of the variant.
+pub trait ZelusErrorFrom<E> {
+    fn variant_of(err: &E) -> Self;
+}
+
+/// Describes an error response, attached to its extensions so layers can rewrite it.
+#[derive(Clone, Debug)]
+pub struct ErrorInfo {
+    pub id: &'static str,
+    pub msg: &'static str,
+    pub code: StatusCode,
+    pub details: Option<serde_json::Value>,
+    /// The numeric code of the error, sent as `code`.
+    pub error_code: Option<u32>,
+    pub help_url: Option<&'static str>,
+    pub retryable: bool,
+    /// Sent as `Retry-After`.
+    pub retry_after: Option<core::time::Duration>,
+}
+
+impl ErrorInfo {
+    /// The payload of the response, with the message replaced by `msg`.
+    #[must_use]
+    pub fn payload(&self, msg: &str) -> serde_json::Value {
+        let mut error = serde_json::json!({
+            "id": self.id,
+            "msg": msg,
+        });
+        if let Some(code) = self.error_code {
+            error["code"] = code.into();
+        }
+        if let Some(help_url) = self.help_url {
+            error["help_url"] = help_url.into();
+        }
+        if let Some(details) = &self.details {
+            error["details"] = details.clone();
+        }
+        serde_json::json!({ "error": error })
+    }
+}
+
+/// How the layers of the router want an error response to be rendered.
+#[derive(Clone, Default)]
+pub(crate) struct ErrorRendering {
+    pub(crate) msg: Option<String>,
+    pub(crate) format: Option<Arc<dyn ErrorFormat>>,
+    pub(crate) instance: Option<String>,
+}
+
+struct DeferredState {
+    info: ErrorInfo,
+    rendering: ErrorRendering,
+    rendered: Option<Bytes>,
+}
+
+impl DeferredState {
+    fn render(&mut self) -> Bytes {
+        self.rendered
+            .get_or_insert_with(|| {
+                let msg = self.rendering.msg.as_deref().unwrap_or(self.info.msg);
+                let payload = self.rendering.format.as_ref().map_or_else(
+                    || self.info.payload(msg),
+                    |format| format.payload(&self.info, msg, self.rendering.instance.as_deref()),
+                );
+                Bytes::from(serde_json::to_vec(&payload).expect("Error payloads are serializable"))
+            })
+            .clone()
+    }
+}
+
+/// The rendering of an error response, shared by its extensions and its body.
+#[derive(Clone)]
+struct DeferredError(Arc<Mutex<DeferredState>>);
+
+impl DeferredError {
+    fn state(&self) -> MutexGuard<'_, DeferredState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Body of an error response, only rendered once it is sent, after the layers changed it.
+struct ErrorBody(Option<DeferredError>);
+
+impl HttpBody for ErrorBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        Poll::Ready(
+            self.0
+                .take()
+                .map(|deferred| Ok(Frame::data(deferred.state().render()))),
+        )
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.as_ref().map_or_else(
+            || SizeHint::with_exact(0),
+            |deferred| SizeHint::with_exact(deferred.state().render().len() as u64),
+        )
+    }
+}
+
+/// Error response in the default format, its body is rendered when it is sent.
+pub(crate) fn error_body(info: &ErrorInfo) -> Response {
+    let deferred = DeferredError(Arc::new(Mutex::new(DeferredState {
+        info: info.clone(),
+        rendering: ErrorRendering::default(),
+        rendered: None,
+    })));
+    let mut response = Response::new(Body::new(ErrorBody(Some(deferred.clone()))));
+    *response.status_mut() = info.code;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response.extensions_mut().insert(deferred);
+    response
+}
+
+/// Changes the [`ErrorRendering`] of an error response, before its body is rendered.
+pub(crate) fn render_error(
+    mut response: Response,
+    update: impl FnOnce(&ErrorInfo, &mut ErrorRendering),
+) -> Response {
+    let Some(deferred) = response.extensions().get::<DeferredError>().cloned() else {
+        return response;
+    };
+    let mut state = deferred.state();
+    let DeferredState {
+        info,
+        rendering,
+        rendered,
+    } = &mut *state;
+    update(info, rendering);
+    *rendered = None;
+    let media_type = rendering
+        .format
+        .as_ref()
+        .map_or("application/json", |format| format.media_type());
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(media_type));
+    drop(state);
+    response
+}
+
+/// A generated error taken apart, to be rebuilt as another generated error.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum ErrorParts {
+    Variant {
+        id: &'static str,
+        msg: &'static str,
+        code: StatusCode,
+        details: Option<Box<dyn core::any::Any + Send + Sync>>,
+    },
+    Communication(crate::sdk::ZelusClientError),
+    Unknown {
+        status: StatusCode,
+        id: Option<String>,
+        msg: Option<String>,
+        body: String,
+        retry_after: Option<core::time::Duration>,
+    },
+    Foreign(Box<ErrorInfo>),
+}
+
+/// Conversions between generated errors for the variants they share, like the errors of a
+/// category, implemented by [`error!`].
+///
+/// Errors without a variant in the other error become its `Foreign` variant, which is rendered
+/// like the original error and keeps it as its source. `From` and `TryFrom` implementations are
+/// generated by [`convert_errors!`](crate::convert_errors), and between the errors of the routes
+/// of a service with `#[service(convert_errors)]`.
+pub trait ZelusErrorConvert: core::error::Error + Send + Sync + Sized + 'static {
+    #[doc(hidden)]
+    fn into_parts(self) -> (ErrorParts, Option<ErrorSource>);
+
+    #[doc(hidden)]
+    fn from_parts(
+        parts: ErrorParts,
+        source: Option<ErrorSource>,
+    ) -> Result<Self, (ErrorParts, Option<ErrorSource>)>;
+
+    #[doc(hidden)]
+    fn foreign(info: ErrorInfo, error: ErrorSource) -> Self;
+
+    #[doc(hidden)]
+    fn info(&self) -> ErrorInfo;
+
+    /// The same variant of `T`, keeping the cause, or the error itself if `T` has none.
+    fn try_convert<T: ZelusErrorConvert>(self) -> Result<T, Self> {
+        let (parts, source) = self.into_parts();
+        T::from_parts(parts, source).map_err(|(parts, source)| {
+            Self::from_parts(parts, source).expect("Errors can be rebuilt from their own parts")
+        })
+    }
+
+    /// The same variant of `T`, keeping the cause, or its `Foreign` variant.
+    fn convert<T: ZelusErrorConvert>(self) -> T {
+        self.try_convert()
+            .unwrap_or_else(|error| T::foreign(error.info(), Box::new(error)))
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct ZelusErrorValue<T: ZelusError + 'static> {
     pub id: &'static str,
     pub msg: &'static str,
     pub code: StatusCode,
     pub instance: &'static T,
+    /// The numeric code of the error, defined with `code = ..` in `define_error!`.
+    pub error_code: Option<u32>,
+    pub help_url: Option<&'static str>,
+    /// The `///` comments of the error in `define_error!`, in Markdown.
+    pub documentation: Option<&'static str>,
+    /// Whether the request may be repeated later, defined with `retryable` or `retry_after = ..`
+    /// in `define_error!`.
+    pub retryable: bool,
 }
 
 pub trait ZelusError: Send + Sync {
     type Error: ZelusError;
 
+    /// Errors carrying details have no static instance and aren't listed.
     fn error_values() -> &'static [ZelusErrorValue<Self::Error>]
     where
         Self: Sized;
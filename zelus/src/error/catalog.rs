@@ -0,0 +1,160 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use utoipa::openapi::{Object, OpenApi, RefOr, Schema};
+
+/// Every error the documented routes can return, collected from their documentation.
+///
+/// Built by [`ZelusRouter::error_catalog`](crate::router::ZelusRouter::error_catalog)
+/// or from a finished [`OpenApi`].
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct ErrorCatalog(BTreeMap<String, ErrorCatalogEntry>);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ErrorCatalogEntry {
+    pub id: String,
+    /// `None` for errors defined inline with `{ Name "msg" STATUS }`.
+    pub category: Option<String>,
+    pub status: u16,
+    pub msg: String,
+    pub operations: BTreeSet<String>,
+}
+
+impl ErrorCatalog {
+    #[must_use]
+    pub fn from_openapi(openapi: &OpenApi) -> Self {
+        let mut catalog = Self::default();
+        for (path, item) in &openapi.paths.paths {
+            for (method, operation) in [
+                ("GET", &item.get),
+                ("PUT", &item.put),
+                ("POST", &item.post),
+                ("DELETE", &item.delete),
+                ("OPTIONS", &item.options),
+                ("HEAD", &item.head),
+                ("PATCH", &item.patch),
+                ("TRACE", &item.trace),
+            ] {
+                let Some(operation) = operation else {
+                    continue;
+                };
+                let operation_id = operation
+                    .operation_id
+                    .clone()
+                    .unwrap_or_else(|| format!("{method} {path}"));
+                for (status, response) in &operation.responses.responses {
+                    let (RefOr::T(response), Ok(status)) = (response, status.parse::<u16>()) else {
+                        continue;
+                    };
+                    for schema in response
+                        .content
+                        .values()
+                        .filter_map(|content| content.schema.as_ref())
+                    {
+                        catalog.collect(schema, status, &operation_id);
+                    }
+                }
+            }
+        }
+        catalog
+    }
+
+    fn collect(&mut self, schema: &RefOr<Schema>, status: u16, operation_id: &str) {
+        match schema {
+            RefOr::T(Schema::OneOf(one_of)) => {
+                for item in &one_of.items {
+                    self.collect(item, status, operation_id);
+                }
+            }
+            RefOr::T(Schema::Object(obj)) => {
+                let Some((id, msg)) = error_constants(obj) else {
+                    return;
+                };
+                let entry = self
+                    .0
+                    .entry(id.clone())
+                    .or_insert_with(|| ErrorCatalogEntry {
+                        category: id
+                            .split_once('/')
+                            .map(|(category, _error)| category.to_owned()),
+                        id,
+                        status,
+                        msg,
+                        operations: BTreeSet::new(),
+                    });
+                // Separate definitions can reuse an id, like two `define_error!` of a category
+                if entry.status != status {
+                    tracing::warn!(
+                        "The error {} is returned with the status {} and {status} by {operation_id}",
+                        entry.id,
+                        entry.status,
+                    );
+                }
+                entry.operations.insert(operation_id.to_owned());
+            }
+            _ => {}
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ErrorCatalogEntry> {
+        self.0.values()
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self.entries().collect::<Vec<_>>())
+            .expect("Error catalog is serializable")
+    }
+
+    /// A table of the errors, for an error reference.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from(
+            "| Id | Category | Status | Message | Operations |\n|---|---|---|---|---|\n",
+        );
+        for entry in self.entries() {
+            let operations = entry
+                .operations
+                .iter()
+                .map(|operation| format!("`{operation}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                markdown,
+                "| `{}` | {} | {} | {} | {operations} |",
+                entry.id,
+                entry.category.as_deref().unwrap_or_default(),
+                entry.status,
+                entry.msg.replace('|', "\\|"),
+            )
+            .expect("Writing to a string can't fail");
+        }
+        markdown
+    }
+}
+
+/// The id and message of an error schema, with the `{"error": {..}}` envelope or a format
+/// with top-level members like problem+json.
+pub(crate) fn error_constants(obj: &Object) -> Option<(String, String)> {
+    if let Some(RefOr::T(Schema::Object(error))) = obj.properties.get("error") {
+        return Some((constant(error, "id")?, constant(error, "msg")?));
+    }
+    Some((
+        constant(obj, "id")?,
+        constant(obj, "msg").or_else(|| constant(obj, "title"))?,
+    ))
+}
+
+/// The only value a property can have.
+pub(crate) fn constant(obj: &Object, name: &str) -> Option<String> {
+    match obj.properties.get(name)? {
+        RefOr::T(Schema::Object(property)) => property
+            .enum_values
+            .as_ref()?
+            .first()?
+            .as_str()
+            .map(ToOwned::to_owned),
+        _ => None,
+    }
+}
@@ -0,0 +1,104 @@
+use crate::error::ErrorInfo;
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::routing::MethodRouter;
+use http::{HeaderName, Method, StatusCode};
+use std::sync::Arc;
+
+/// An error response seen by the hook of
+/// [`ZelusRouter::on_error`](crate::router::ZelusRouter::on_error).
+#[derive(Clone, Copy)]
+pub struct ErrorContext<'a> {
+    /// `None` for routes without an operation id and responses of undocumented routes.
+    pub operation_id: Option<&'a str>,
+    /// The path template of the route, like `/users/{id}`.
+    pub path: Option<&'a str>,
+    pub method: &'a Method,
+    pub id: &'static str,
+    pub status: StatusCode,
+    /// The `x-request-id` header of the request, or the header passed to
+    /// [`ZelusRouter::on_error_with_request_id`](crate::router::ZelusRouter::on_error_with_request_id).
+    pub request_id: Option<&'a str>,
+    pub info: &'a ErrorInfo,
+    /// The generated error the response was created from.
+    pub error: Option<&'a (dyn core::error::Error + 'static)>,
+}
+
+impl<'a> ErrorContext<'a> {
+    /// The cause kept by the error with `with_source` or `?`, or the error of a failed
+    /// request to another service.
+    #[must_use]
+    pub fn source(&self) -> Option<&'a (dyn core::error::Error + 'static)> {
+        self.error?.source()
+    }
+}
+
+pub(crate) type ErrorHook = dyn Fn(&ErrorContext<'_>) + Send + Sync;
+
+/// The hook of [`ZelusRouter::on_error`](crate::router::ZelusRouter::on_error) and the header
+/// it reads the request id from.
+pub(crate) struct ErrorReporter {
+    pub(crate) hook: Box<ErrorHook>,
+    pub(crate) request_id: HeaderName,
+}
+
+/// The generated error of a response, attached to its extensions.
+#[derive(Clone)]
+pub(crate) struct KeptError(pub(crate) Arc<dyn core::error::Error + Send + Sync>);
+
+/// The operation id of the route an error response was created by.
+#[derive(Clone)]
+struct OperationId(Arc<str>);
+
+/// Attaches the operation id to the error responses of the route, for the hooks added after it.
+pub(crate) fn tag_operation(router: MethodRouter, operation_id: Option<&str>) -> MethodRouter {
+    let Some(operation_id) = operation_id else {
+        return router;
+    };
+    router.layer(axum::middleware::map_response_with_state(
+        OperationId(Arc::from(operation_id)),
+        tag_response,
+    ))
+}
+
+async fn tag_response(State(operation_id): State<OperationId>, mut response: Response) -> Response {
+    if response.extensions().get::<ErrorInfo>().is_some() {
+        response.extensions_mut().insert(operation_id);
+    }
+    response
+}
+
+pub(crate) async fn report_errors(
+    State(reporter): State<Arc<ErrorReporter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request.extensions().get::<MatchedPath>().cloned();
+    let request_id = request
+        .headers()
+        .get(&reporter.request_id)
+        .and_then(|request_id| request_id.to_str().ok())
+        .map(ToOwned::to_owned);
+    let response = next.run(request).await;
+    if let Some(info) = response.extensions().get::<ErrorInfo>() {
+        (reporter.hook)(&ErrorContext {
+            operation_id: response
+                .extensions()
+                .get::<OperationId>()
+                .map(|operation_id| &*operation_id.0),
+            path: path.as_ref().map(MatchedPath::as_str),
+            method: &method,
+            id: info.id,
+            status: info.code,
+            request_id: request_id.as_deref(),
+            info,
+            error: response
+                .extensions()
+                .get::<KeptError>()
+                .map(|error| &*error.0 as &(dyn core::error::Error + 'static)),
+        });
+    }
+    response
+}
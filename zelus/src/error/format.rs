@@ -0,0 +1,214 @@
+use crate::error::catalog::constant;
+use crate::error::{ErrorInfo, render_error};
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use http::{HeaderMap, HeaderName, StatusCode};
+use std::sync::Arc;
+use utoipa::openapi::schema::OneOf;
+use utoipa::openapi::{Content, Object, OpenApi, RefOr, Schema, Type};
+
+/// Body format of error responses, applied with
+/// [`ZelusRouter::error_format`](crate::router::ZelusRouter::error_format).
+///
+/// Without one, errors are rendered as `{"error": {"id", "msg", "details"}}`.
+pub trait ErrorFormat: Send + Sync + 'static {
+    fn media_type(&self) -> &'static str;
+
+    /// Identifies the occurrence of the error, usually a trace id of the request.
+    fn instance(&self, _request: &HeaderMap) -> Option<String> {
+        None
+    }
+
+    fn payload(&self, info: &ErrorInfo, msg: &str, instance: Option<&str>) -> serde_json::Value;
+
+    /// `error` is the schema of the `error` member of the default format, with the `details`,
+    /// `code` and `help_url` of the error.
+    fn schema(&self, id: &str, msg: &str, code: StatusCode, error: &Object) -> Schema;
+}
+
+/// RFC 9457 `application/problem+json` error bodies.
+///
+/// The id of the error is appended to `type_base` to build the `type` URI and is also kept as the
+/// `id` extension member, so the SDK can decode the error. Details, the numeric code and the help
+/// link are kept as `details`, `code` and `help_url`.
+#[derive(Clone)]
+pub struct ProblemJson {
+    type_base: String,
+    trace_header: HeaderName,
+}
+
+impl ProblemJson {
+    pub const MEDIA_TYPE: &str = "application/problem+json";
+
+    /// Uses `x-request-id` of the request as `instance`.
+    #[must_use]
+    pub fn new(type_base: &str) -> Self {
+        Self {
+            type_base: type_base.to_owned(),
+            trace_header: HeaderName::from_static("x-request-id"),
+        }
+    }
+
+    /// Request header containing the trace id used as `instance`.
+    #[must_use]
+    pub fn trace_header(mut self, trace_header: HeaderName) -> Self {
+        self.trace_header = trace_header;
+        self
+    }
+}
+
+impl ErrorFormat for ProblemJson {
+    fn media_type(&self) -> &'static str {
+        Self::MEDIA_TYPE
+    }
+
+    fn instance(&self, request: &HeaderMap) -> Option<String> {
+        request
+            .get(&self.trace_header)
+            .and_then(|trace| trace.to_str().ok())
+            .map(ToOwned::to_owned)
+    }
+
+    fn payload(&self, info: &ErrorInfo, msg: &str, instance: Option<&str>) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "type": format!("{}{}", self.type_base, info.id),
+            "title": msg,
+            "status": info.code.as_u16(),
+            "id": info.id,
+        });
+        if let Some(instance) = instance {
+            payload["instance"] = instance.into();
+        }
+        if let Some(code) = info.error_code {
+            payload["code"] = code.into();
+        }
+        if let Some(help_url) = info.help_url {
+            payload["help_url"] = help_url.into();
+        }
+        if let Some(details) = &info.details {
+            payload["details"] = details.clone();
+        }
+        payload
+    }
+
+    fn schema(&self, id: &str, msg: &str, code: StatusCode, error: &Object) -> Schema {
+        let mut obj = Object::with_type(Type::Object);
+        for (name, value, ty) in [
+            (
+                "type",
+                serde_json::Value::from(format!("{}{id}", self.type_base)),
+                Type::String,
+            ),
+            ("title", msg.into(), Type::String),
+            ("status", code.as_u16().into(), Type::Integer),
+            ("id", id.into(), Type::String),
+        ] {
+            let mut property = Object::with_type(ty);
+            property.example = Some(value.clone());
+            property.enum_values = Some(vec![value]);
+            obj.properties
+                .insert(name.to_owned(), RefOr::T(Schema::Object(property)));
+            obj.required.push(name.to_owned());
+        }
+        obj.properties.insert(
+            "instance".to_owned(),
+            RefOr::T(Schema::Object(Object::with_type(Type::String))),
+        );
+        for name in ["code", "help_url", "details"] {
+            if let Some(property) = error.properties.get(name) {
+                obj.properties.insert(name.to_owned(), property.clone());
+                obj.required.push(name.to_owned());
+            }
+        }
+        Schema::Object(obj)
+    }
+}
+
+pub(crate) async fn format_errors(
+    State(format): State<Arc<dyn ErrorFormat>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let instance = format.instance(request.headers());
+    let response = next.run(request).await;
+    render_error(response, |_info, rendering| {
+        rendering.format = Some(format);
+        rendering.instance = instance;
+    })
+}
+
+/// Rewrites the documented error responses to the format.
+pub(crate) fn document_format(openapi: &mut OpenApi, format: &dyn ErrorFormat) {
+    for item in openapi.paths.paths.values_mut() {
+        for operation in [
+            &mut item.get,
+            &mut item.put,
+            &mut item.post,
+            &mut item.delete,
+            &mut item.options,
+            &mut item.head,
+            &mut item.patch,
+            &mut item.trace,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for (status, response) in &mut operation.responses.responses {
+                let (RefOr::T(response), Ok(status)) = (response, status.parse::<StatusCode>())
+                else {
+                    continue;
+                };
+                let Some(schema) = response
+                    .content
+                    .get("application/json")
+                    .and_then(|content| content.schema.as_ref())
+                    .and_then(|schema| convert_schema(format, schema, status))
+                else {
+                    continue;
+                };
+                response.content.shift_remove("application/json");
+                response
+                    .content
+                    .insert(format.media_type().to_owned(), Content::new(Some(schema)));
+            }
+        }
+    }
+}
+
+/// Converts the schema of an error, or of errors sharing a status, documented by `error_schema`.
+fn convert_schema(
+    format: &dyn ErrorFormat,
+    schema: &RefOr<Schema>,
+    status: StatusCode,
+) -> Option<RefOr<Schema>> {
+    match schema {
+        RefOr::T(Schema::OneOf(one_of)) => {
+            let mut converted = OneOf::new();
+            converted.items = one_of
+                .items
+                .iter()
+                .map(|item| convert_schema(format, item, status))
+                .collect::<Option<_>>()?;
+            Some(RefOr::T(Schema::OneOf(converted)))
+        }
+        RefOr::T(Schema::Object(obj)) => {
+            let RefOr::T(Schema::Object(error)) = obj.properties.get("error")? else {
+                return None;
+            };
+            let mut schema = format.schema(
+                &constant(error, "id")?,
+                &constant(error, "msg")?,
+                status,
+                error,
+            );
+            if let Schema::Object(converted) = &mut schema
+                && converted.description.is_none()
+            {
+                converted.description.clone_from(&obj.description);
+            }
+            Some(RefOr::T(schema))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,199 @@
+use crate::codec::BodyFormat;
+use crate::responses::WithStatus;
+use crate::sdk::ZelusClientError;
+use crate::utils::MaybeUnit;
+use axum::extract::Request;
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use core::marker::PhantomData;
+use http::header::{ACCEPT, CONTENT_TYPE};
+use http::request::Parts;
+use http::{HeaderMap, StatusCode};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use utoipa::openapi::request_body::RequestBody;
+use utoipa::openapi::{Content, RefOr, Required, ResponsesBuilder};
+
+pub struct FrameworkResponse<T>(pub T, pub BodyFormat);
+
+pub trait IntoFrameworkResponse {
+    fn into_framework_response(self, format: BodyFormat) -> Response;
+
+    /// Whether the response may have a serialized body, which the `Accept` header is checked for.
+    #[must_use]
+    fn has_body() -> bool {
+        true
+    }
+}
+
+pub trait FromFrameworkResponse: Sized {
+    /// Whether the response may be a redirect, which the SDK must receive instead of following.
+    const REDIRECT: bool = false;
+
+    fn from_framework_response(
+        response: reqwest::Response,
+    ) -> impl Future<Output = Result<Self, ZelusClientError>> + Send;
+}
+
+/// Result type of a route, to find the type the SDK decodes.
+pub trait RouteResult {
+    type Success;
+}
+
+impl<T, E> RouteResult for Result<T, E> {
+    type Success = T;
+}
+
+/// Result type of a route using the `status = ..` option, to document its status code.
+pub trait RouteStatus<const CODE: u16> {
+    type Output;
+}
+
+impl<const CODE: u16, T, E> RouteStatus<CODE> for Result<T, E> {
+    type Output = Result<WithStatus<CODE, T>, E>;
+}
+
+impl<T: Serialize + MaybeUnit + 'static> IntoFrameworkResponse for T {
+    fn into_framework_response(self, format: BodyFormat) -> Response {
+        if T::unit().is_some() {
+            StatusCode::NO_CONTENT.into_response()
+        } else {
+            match format.serialize(&self) {
+                Ok(body) => ([(CONTENT_TYPE, format.media_type())], body).into_response(),
+                Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+            }
+        }
+    }
+
+    fn has_body() -> bool {
+        T::unit().is_none()
+    }
+}
+
+impl<T: DeserializeOwned + MaybeUnit + Send + 'static> FromFrameworkResponse for T {
+    async fn from_framework_response(
+        response: reqwest::Response,
+    ) -> Result<Self, ZelusClientError> {
+        if let Some(unit) = T::unit() {
+            Ok(unit)
+        } else {
+            let format = content_type(response.headers()).unwrap_or_default();
+            Ok(format.deserialize(&response.bytes().await?)?)
+        }
+    }
+}
+
+impl<T: IntoFrameworkResponse> IntoResponse for FrameworkResponse<T> {
+    fn into_response(self) -> Response {
+        self.0.into_framework_response(self.1)
+    }
+}
+
+impl<T: FromFrameworkResponse> FrameworkResponse<T> {
+    pub async fn from_reqwest(response: reqwest::Response) -> Result<Self, ZelusClientError> {
+        let format = content_type(response.headers()).unwrap_or_default();
+        T::from_framework_response(response)
+            .await
+            .map(|value| Self(value, format))
+    }
+}
+
+fn content_type(headers: &HeaderMap) -> Option<BodyFormat> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(BodyFormat::from_media_type)
+}
+
+/// Body format of the response `T`, negotiated from the `Accept` header.
+///
+/// Responses without a body are sent whatever the client accepts.
+pub struct Accept<T>(pub BodyFormat, pub PhantomData<fn() -> T>);
+
+impl<T: IntoFrameworkResponse, S: Send + Sync> axum::extract::FromRequestParts<S> for Accept<T> {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if !T::has_body() {
+            return Ok(Self(BodyFormat::default(), PhantomData));
+        }
+        BodyFormat::negotiate(parts.headers.get(ACCEPT))
+            .map(|format| Self(format, PhantomData))
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_ACCEPTABLE,
+                    "None of the accepted media types is supported",
+                )
+                    .into_response()
+            })
+    }
+}
+
+/// Request payload, decoded according to the `Content-Type` header.
+pub struct Payload<T>(pub T);
+
+impl<T: DeserializeOwned, S: Send + Sync> axum::extract::FromRequest<S> for Payload<T> {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(format) = content_type(req.headers()) else {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "The content type of the payload is not supported",
+            )
+                .into_response());
+        };
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        format
+            .deserialize(&bytes)
+            .map(Self)
+            .map_err(|err| (StatusCode::BAD_REQUEST, err.to_string()).into_response())
+    }
+}
+
+pub fn payload<T: Serialize + ?Sized>(
+    request: reqwest::RequestBuilder,
+    format: BodyFormat,
+    value: &T,
+) -> Result<reqwest::RequestBuilder, ZelusClientError> {
+    Ok(request
+        .header(CONTENT_TYPE, format.media_type())
+        .body(format.serialize(value)?))
+}
+
+/// Documents the payload for every supported body format.
+#[must_use]
+pub fn payload_request_body(content: &Content) -> RequestBody {
+    let mut request_body = RequestBody::builder().required(Some(Required::True));
+    for format in BodyFormat::ALL {
+        request_body = request_body.content(format.media_type(), content.clone());
+    }
+    request_body.build()
+}
+
+/// Moves every documented response to `status`, optionally replacing its description.
+#[must_use]
+pub fn document_status(
+    mut responses: ResponsesBuilder,
+    documented: ResponsesBuilder,
+    status: StatusCode,
+    description: Option<&str>,
+) -> ResponsesBuilder {
+    for (_code, response) in documented.build().responses {
+        responses = responses.response(
+            status.as_str(),
+            match response {
+                RefOr::T(mut response) => {
+                    if let Some(description) = description {
+                        description.clone_into(&mut response.description);
+                    }
+                    RefOr::T(response)
+                }
+                RefOr::Ref(reference) => RefOr::Ref(reference),
+            },
+        );
+    }
+    responses
+}
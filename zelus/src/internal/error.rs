@@ -1,11 +1,44 @@
-use core::str::FromStr;
-use serde::de::Error as _;
-use serde::{Deserialize, Deserializer};
-use utoipa::openapi::{Object, RefOr, Schema, Type};
+use crate::codec::CodecError;
+use crate::sdk::ZelusClientError;
+use core::time::Duration;
+use http::header::{CONTENT_TYPE, RETRY_AFTER};
+use http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::SystemTime;
+use utoipa::openapi::header::HeaderBuilder;
+use utoipa::openapi::schema::OneOf;
+use utoipa::openapi::{Content, Object, RefOr, Response, ResponsesBuilder, Schema, Type};
+
+/// Rebuilds a generated error from the `id` and `details` of its payload.
+pub trait ErrorPayload: Sized {
+    /// Returns `None` for unknown ids.
+    fn from_payload(
+        id: &str,
+        details: serde_json::Value,
+    ) -> Option<Result<Self, serde_json::Error>>;
+
+    /// Error responses with an unknown id or without an error payload.
+    fn unknown(
+        status: StatusCode,
+        id: Option<String>,
+        msg: Option<String>,
+        body: String,
+        retry_after: Option<Duration>,
+    ) -> Self;
+}
 
 #[must_use]
-pub fn error_schema(id: &str, msg: &str) -> Schema {
+pub fn error_schema(
+    id: &str,
+    msg: &str,
+    details: Option<RefOr<Schema>>,
+    code: Option<u32>,
+    help_url: Option<&str>,
+    documentation: Option<&str>,
+) -> Schema {
     let mut obj = Object::with_type(Type::Object);
+    obj.description = documentation.map(ToOwned::to_owned);
     let mut error = Object::with_type(Type::Object);
 
     let mut id_obj = Object::with_type(Type::String);
@@ -22,42 +55,252 @@ pub fn error_schema(id: &str, msg: &str) -> Schema {
         .properties
         .insert("msg".to_owned(), RefOr::T(Schema::Object(msg_obj)));
 
+    for (name, value, ty) in [
+        ("code", code.map(serde_json::Value::from), Type::Integer),
+        (
+            "help_url",
+            help_url.map(serde_json::Value::from),
+            Type::String,
+        ),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let mut property = Object::with_type(ty);
+        property.example = Some(value.clone());
+        property.enum_values = Some(vec![value]);
+        error
+            .properties
+            .insert(name.to_owned(), RefOr::T(Schema::Object(property)));
+        error.required.push(name.to_owned());
+    }
+
+    if let Some(details) = details {
+        error.properties.insert("details".to_owned(), details);
+        error.required.push("details".to_owned());
+    }
+
     obj.properties
         .insert("error".to_owned(), RefOr::T(Schema::Object(error)));
     Schema::Object(obj)
 }
 
-pub async fn error_by_response<T: FromStr<Err = ()> + From<reqwest::Error>>(
-    response: reqwest::Response,
-) -> T {
-    struct FromStrData<T: FromStr<Err = ()>>(T);
-    impl<'de, T: FromStr<Err = ()>> Deserialize<'de> for FromStrData<T> {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let id = String::deserialize(deserializer)?;
-            T::from_str(&id)
-                .map(FromStrData)
-                .map_err(|()| D::Error::custom("Error not found!"))
+/// Statuses of overloaded servers and gateways, which may succeed if repeated.
+#[must_use]
+pub const fn retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Documents the errors, merging the ones sharing a status into a `oneOf` response.
+///
+/// The `bool` of an error is whether it sends `Retry-After`.
+#[must_use]
+pub fn document_errors(
+    mut responses: ResponsesBuilder,
+    errors: Vec<(StatusCode, &str, Schema, bool)>,
+) -> ResponsesBuilder {
+    let mut by_status = BTreeMap::<_, Vec<_>>::new();
+    let mut retry_after = BTreeSet::new();
+    for (status, msg, schema, sends_retry_after) in errors {
+        by_status.entry(status).or_default().push((msg, schema));
+        if sends_retry_after {
+            retry_after.insert(status);
+        }
+    }
+
+    for (status, errors) in by_status {
+        let description = if let [(msg, _schema)] = errors.as_slice() {
+            (*msg).to_owned()
+        } else {
+            errors
+                .iter()
+                .map(|(msg, _schema)| format!("- {msg}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let mut schemas = errors.into_iter().map(|(_msg, schema)| schema);
+        let schema = if schemas.len() == 1 {
+            schemas.next().expect("Length was checked")
+        } else {
+            let mut one_of = OneOf::new();
+            one_of.items = schemas.map(RefOr::T).collect();
+            Schema::OneOf(one_of)
+        };
+        let mut response = Response::builder()
+            .description(description)
+            .content("application/json", Content::new(Some(RefOr::T(schema))));
+        if retry_after.contains(&status) {
+            response = response.header(
+                RETRY_AFTER.as_str(),
+                HeaderBuilder::new()
+                    .schema(Schema::Object(Object::with_type(Type::Integer)))
+                    .description(Some("Seconds to wait before repeating the request"))
+                    .build(),
+            );
         }
+        responses = responses.response(status.as_str(), response.build());
     }
+    responses
+}
 
+/// Serializes the details of an error, answering without them if they fail to serialize.
+pub fn error_details(id: &str, details: &impl serde::Serialize) -> Option<serde_json::Value> {
+    serde_json::to_value(details)
+        .inspect_err(|err| {
+            tracing::warn!("Unable to serialize the details of the error {id}: {err}");
+        })
+        .ok()
+}
+
+/// Answers with `502 Bad Gateway` for a failed request to another service that wasn't converted,
+/// with the status, id and message of its error response if there was one.
+#[cfg(feature = "error")]
+#[must_use]
+pub fn upstream_error(
+    status: Option<StatusCode>,
+    id: Option<&str>,
+    msg: Option<&str>,
+    retryable: bool,
+) -> crate::error::ErrorInfo {
+    crate::error::ErrorInfo {
+        id: "upstream",
+        msg: "Request to another service failed",
+        code: StatusCode::BAD_GATEWAY,
+        details: status
+            .map(|status| serde_json::json!({ "status": status.as_u16(), "id": id, "msg": msg })),
+        error_code: None,
+        help_url: None,
+        retryable,
+        retry_after: None,
+    }
+}
+
+#[cfg(feature = "error")]
+#[must_use]
+pub fn error_response(
+    info: crate::error::ErrorInfo,
+    error: impl core::error::Error + Send + Sync + 'static,
+) -> axum::response::Response {
+    let mut response = crate::error::error_body(&info);
+    if let Some(retry_after) = info.retry_after {
+        // Rounded up, waiting less than requested could fail again
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response.headers_mut().insert(RETRY_AFTER, seconds.into());
+    }
+    response.extensions_mut().insert(info);
+    response
+        .extensions_mut()
+        .insert(crate::error::context::KeptError(std::sync::Arc::new(error)));
+    response
+}
+
+/// Whether `key` is an entry of a custom error attribute, usable in constants.
+#[cfg(feature = "error")]
+#[must_use]
+pub const fn source_key<T: crate::error::ZelusErrorSource>(key: &str) -> bool {
+    let mut index = 0;
+    while index < T::KEYS.len() {
+        let candidate = T::KEYS[index].as_bytes();
+        let key = key.as_bytes();
+        if candidate.len() == key.len() {
+            let mut byte = 0;
+            while byte < key.len() && candidate[byte] == key[byte] {
+                byte += 1;
+            }
+            if byte == key.len() {
+                return true;
+            }
+        }
+        index += 1;
+    }
+    false
+}
+
+/// Name of the violated constraint.
+///
+/// The sqlite driver doesn't report it, so with the `sqlite` feature the message of its errors is
+/// used instead, which contains the name of check constraints and the columns of unique
+/// constraints, like `users.email`.
+#[cfg(feature = "sqlx")]
+pub fn sql_constraint(database: &dyn sqlx::error::DatabaseError) -> Option<&str> {
+    #[cfg(feature = "sqlite")]
+    if database.constraint().is_none()
+        && database
+            .try_downcast_ref::<sqlx::sqlite::SqliteError>()
+            .is_some()
+    {
+        return database
+            .message()
+            .split_once("constraint failed: ")
+            .map(|(_kind, name)| name);
+    }
+    database.constraint()
+}
+
+/// Decodes the error from the `{"error": {..}}` envelope, or from the top-level members of
+/// `application/problem+json` responses.
+///
+/// Only failing to receive the body is a communication error, bodies which aren't a known
+/// error are kept by the `Unknown` variant.
+pub async fn error_by_response<T: ErrorPayload + From<ZelusClientError>>(
+    response: reqwest::Response,
+) -> T {
     #[derive(Deserialize)]
-    struct ErrorContent<T> {
-        id: T,
+    struct ErrorContent {
+        id: String,
+        #[serde(alias = "title")]
+        msg: Option<String>,
+        #[serde(default)]
+        details: serde_json::Value,
     }
     #[derive(Deserialize)]
-    struct ErrorStruct<T> {
-        error: ErrorContent<T>,
+    struct ErrorStruct {
+        error: ErrorContent,
     }
 
-    match response.json().await {
-        Ok(ErrorStruct {
-            error: ErrorContent {
-                id: FromStrData(err),
-            },
-        }) => err,
-        Err(err) => T::from(err),
+    let status = response.status();
+    let retry_after = retry_after(response.headers());
+    let problem = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/problem+json"));
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(err) => return T::from(ZelusClientError::Reqwest(err)),
+    };
+    let content = if problem {
+        serde_json::from_str::<ErrorContent>(&body)
+    } else {
+        serde_json::from_str::<ErrorStruct>(&body).map(|content| content.error)
+    };
+    match content {
+        Ok(ErrorContent { id, msg, details }) => match T::from_payload(&id, details) {
+            Some(Ok(err)) => err,
+            Some(Err(err)) => T::from(ZelusClientError::Codec(CodecError(Box::new(err)))),
+            None => T::unknown(status, Some(id), msg, body, retry_after),
+        },
+        // Like a proxy answering with an error page
+        Err(_err) => T::unknown(status, None, None, body, retry_after),
     }
 }
+
+/// The `Retry-After` header, in seconds or as a date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    value.parse().map_or_else(
+        |_err| {
+            httpdate::parse_http_date(value)
+                .ok()?
+                .duration_since(SystemTime::now())
+                .ok()
+        },
+        |seconds| Some(Duration::from_secs(seconds)),
+    )
+}
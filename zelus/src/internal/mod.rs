@@ -1,12 +1,21 @@
+mod body;
 mod error;
 mod header;
-mod json;
 mod serializer;
 mod special;
 
-pub use error::{error_by_response, error_schema};
+pub use body::{
+    Accept, FrameworkResponse, FromFrameworkResponse, IntoFrameworkResponse, Payload, RouteResult,
+    RouteStatus, document_status, payload, payload_request_body,
+};
+#[cfg(feature = "sqlx")]
+pub use error::sql_constraint;
+pub use error::{
+    ErrorPayload, document_errors, error_by_response, error_details, error_schema, retryable_status,
+};
+#[cfg(feature = "error")]
+pub use error::{error_response, source_key, upstream_error};
 pub use header::{header_insert, header_name};
-pub use json::FrameworkJsonResponse;
 pub use serializer::StringSerializer;
 pub use special::AxumSpecialWrapper;
 
@@ -4,6 +4,8 @@
     reason = "This order is more readable"
 )]
 
+pub mod codec;
+pub mod cors;
 pub mod sdk;
 pub mod types;
 
@@ -28,6 +30,7 @@ pub use serde_urlencoded;
 #[cfg(feature = "sqlx")]
 pub use sqlx;
 pub use tap;
+pub use tokio;
 pub use url;
 pub use urlencoding;
 pub use utoipa;
@@ -35,6 +38,8 @@ pub use utoipa_axum;
 pub use zelus_macros::service;
 #[cfg(feature = "error")]
 pub mod error;
+#[cfg(feature = "error")]
+pub mod locale;
 
 extern crate self as zelus;
 
@@ -79,6 +84,77 @@ macro_rules! define_header_variable {
     };
 }
 
+/// Registers an error type implementing [`ZelusErrorSource`](crate::error::ZelusErrorSource)
+/// as the error attribute `$name`, like `[diesel:not_found]`.
+///
+/// Attributes which are neither built in nor registered in scope fail to resolve the module
+/// `error_source_$name` at the attribute.
+#[cfg(feature = "error")]
+#[macro_export]
+macro_rules! define_error_source {
+    ($name:ident $source:ty) => {
+        $crate::paste! {
+            #[allow(unused_imports)]
+            pub mod [< error_source_ $name:snake >] {
+                use super::*;
+
+                pub type Source = $source;
+
+            }
+        }
+    };
+}
+
+/// Implements `From` between generated errors, or `TryFrom` with `try`, so `?` works across
+/// them. See [`ZelusErrorConvert`](crate::error::ZelusErrorConvert) for the variants without a
+/// counterpart.
+///
+/// ```
+/// use zelus::convert_errors;
+/// use zelus::error::{ErrorCause, define_error, error};
+///
+/// define_error!(category {
+///     error1 ("This is the error message for error1" NOT_FOUND),
+///     error2 ("This is the error message for error2" CONFLICT),
+/// });
+///
+/// error!(NarrowError, category(error1));
+/// error!(WideError, category(error1 error2));
+///
+/// convert_errors!(NarrowError => WideError);
+/// convert_errors!(try WideError => NarrowError);
+///
+/// let error = WideError::from(NarrowError::CategoryError1(ErrorCause::none()));
+/// assert!(matches!(error, WideError::CategoryError1(_)));
+/// assert!(NarrowError::try_from(WideError::CategoryError2(ErrorCause::none())).is_err());
+/// ```
+#[cfg(feature = "error")]
+#[macro_export]
+macro_rules! convert_errors {
+    (try $from:ty => $($into:ty),+ $(,)?) => {
+        $(
+            #[automatically_derived]
+            impl TryFrom<$from> for $into {
+                type Error = $from;
+
+                fn try_from(error: $from) -> Result<Self, $from> {
+                    $crate::error::ZelusErrorConvert::try_convert(error)
+                }
+            }
+        )+
+    };
+    ($from:ty => $($into:ty),+ $(,)?) => {
+        $(
+            #[automatically_derived]
+            impl From<$from> for $into {
+                fn from(error: $from) -> Self {
+                    $crate::error::ZelusErrorConvert::convert(error)
+                }
+            }
+        )+
+    };
+}
+
 #[macro_export]
 macro_rules! framework_router {
     ($base:tt $variable:ident ($($variant:ident,)* ) $content:tt) => {
@@ -0,0 +1,120 @@
+use crate::error::{ErrorInfo, render_error};
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use http::HeaderValue;
+use http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, VARY};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Translations of error messages, selected by the `Accept-Language` header of the request.
+///
+/// The messages of `define_error!` and `error!` form the default locale, which is used for
+/// errors without a translation and is the one listed in the documentation. Ids never change.
+#[must_use]
+#[derive(Clone, Default)]
+pub struct MessageCatalog {
+    fallback: Option<String>,
+    locales: HashMap<String, HashMap<String, String>>,
+}
+
+impl MessageCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locale used if none of the accepted languages is available.
+    pub fn fallback(mut self, locale: &str) -> Self {
+        self.fallback = Some(locale.to_ascii_lowercase());
+        self
+    }
+
+    /// Adds the messages of a locale, by error id.
+    pub fn locale<K: Into<String>, V: Into<String>>(
+        mut self,
+        locale: &str,
+        messages: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        self.locales
+            .entry(locale.to_ascii_lowercase())
+            .or_default()
+            .extend(
+                messages
+                    .into_iter()
+                    .map(|(id, msg)| (id.into(), msg.into())),
+            );
+        self
+    }
+
+    /// Adds a locale from a JSON object mapping error ids to messages,
+    /// embedded with `include_str!` or read at startup.
+    pub fn locale_json(self, locale: &str, json: &str) -> Result<Self, serde_json::Error> {
+        let messages: HashMap<String, String> = serde_json::from_str(json)?;
+        Ok(self.locale(locale, messages))
+    }
+
+    /// Picks the available locale with the highest quality from an `Accept-Language` header.
+    fn negotiate(&self, accept_language: Option<&HeaderValue>) -> Option<&str> {
+        let mut best: Option<(&str, f32)> = None;
+        for range in accept_language
+            .and_then(|accept_language| accept_language.to_str().ok())
+            .unwrap_or_default()
+            .split(',')
+        {
+            let mut parts = range.split(';');
+            let language = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality <= 0.0 || best.is_some_and(|(_locale, best_quality)| quality <= best_quality)
+            {
+                continue;
+            }
+            let primary = language.split('-').next().unwrap_or_default();
+            let locale = self
+                .locales
+                .get_key_value(&language)
+                .or_else(|| self.locales.get_key_value(primary))
+                .map(|(locale, _messages)| locale.as_str());
+            if let Some(locale) = locale {
+                best = Some((locale, quality));
+            }
+        }
+        best.map(|(locale, _quality)| locale)
+            .or(self.fallback.as_deref())
+    }
+}
+
+/// Replaces the message of error responses with the one of the negotiated locale.
+pub(crate) async fn localize_errors(
+    State(catalog): State<Arc<MessageCatalog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let locale = catalog
+        .negotiate(request.headers().get(ACCEPT_LANGUAGE))
+        .map(ToOwned::to_owned);
+    let mut response = next.run(request).await;
+    let Some(id) = response.extensions().get::<ErrorInfo>().map(|info| info.id) else {
+        return response;
+    };
+    // Also untranslated, so that caches don't answer other languages with this message
+    response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept-language"));
+    let Some((locale, msg)) = locale.and_then(|locale| {
+        let msg = catalog.locales.get(&locale)?.get(id)?.clone();
+        Some((locale, msg))
+    }) else {
+        return response;
+    };
+
+    let mut response = render_error(response, |_info, rendering| {
+        rendering.msg = Some(msg);
+    });
+    if let Ok(locale) = HeaderValue::try_from(locale) {
+        response.headers_mut().insert(CONTENT_LANGUAGE, locale);
+    }
+    response
+}
@@ -0,0 +1,177 @@
+use crate::codec::BodyFormat;
+use crate::internal::{FromFrameworkResponse, IntoFrameworkResponse};
+use crate::responses::DocumentedResultResponse;
+use crate::sdk::ZelusClientError;
+use axum::response::Response;
+use axum_extra::TypedHeader;
+use axum_extra::headers::{Header, HeaderMapExt as _};
+use http::HeaderMap;
+use std::collections::{BTreeMap, HashMap};
+use utoipa::openapi::header::HeaderBuilder;
+use utoipa::openapi::{Object, RefOr, ResponsesBuilder, Schema, Type};
+
+/// Success response with additional typed headers.
+///
+/// `H` is either a [`TypedHeader`], an [`Option`] of it, a tuple of those or a struct deriving
+/// [`ResponseHeaders`](zelus_macros::ResponseHeaders).
+pub struct WithHeaders<T, H: ResponseHeaders>(pub T, pub H);
+
+pub trait ResponseHeaders: Sized {
+    fn insert(self, map: &mut HeaderMap);
+
+    fn extract(map: &HeaderMap) -> Result<Self, ZelusClientError>;
+
+    fn openapi(
+        headers: &mut BTreeMap<String, utoipa::openapi::Header>,
+        description: Option<&'static str>,
+    );
+}
+
+impl<T: Header> ResponseHeaders for TypedHeader<T> {
+    fn insert(self, map: &mut HeaderMap) {
+        map.typed_insert(self.0);
+    }
+
+    fn extract(map: &HeaderMap) -> Result<Self, ZelusClientError> {
+        map.typed_get()
+            .map(TypedHeader)
+            .ok_or_else(|| ZelusClientError::Header(T::name()))
+    }
+
+    fn openapi(
+        headers: &mut BTreeMap<String, utoipa::openapi::Header>,
+        description: Option<&'static str>,
+    ) {
+        headers.insert(
+            T::name().as_str().to_owned(),
+            HeaderBuilder::new()
+                .schema(Schema::Object(Object::with_type(Type::String)))
+                .description(description)
+                .build(),
+        );
+    }
+}
+
+impl<T: Header> ResponseHeaders for Option<TypedHeader<T>> {
+    fn insert(self, map: &mut HeaderMap) {
+        if let Some(header) = self {
+            header.insert(map);
+        }
+    }
+
+    fn extract(map: &HeaderMap) -> Result<Self, ZelusClientError> {
+        map.typed_try_get()
+            .map(|header| header.map(TypedHeader))
+            .map_err(|_err| ZelusClientError::Header(T::name()))
+    }
+
+    // The `Header` of utoipa has no `required`, the description marks the header as optional
+    fn openapi(
+        headers: &mut BTreeMap<String, utoipa::openapi::Header>,
+        description: Option<&'static str>,
+    ) {
+        headers.insert(
+            T::name().as_str().to_owned(),
+            HeaderBuilder::new()
+                .schema(Schema::Object(Object::with_type(Type::String)))
+                .description(Some(description.map_or_else(
+                    || "Optional, not sent in every response".to_owned(),
+                    |description| format!("{description} (optional, not sent in every response)"),
+                )))
+                .build(),
+        );
+    }
+}
+
+macro_rules! response_headers_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: ResponseHeaders),+> ResponseHeaders for ($($name,)+) {
+            #[expect(non_snake_case, reason = "Reusing the generic names")]
+            fn insert(self, map: &mut HeaderMap) {
+                let ($($name,)+) = self;
+                $($name.insert(map);)+
+            }
+
+            fn extract(map: &HeaderMap) -> Result<Self, ZelusClientError> {
+                Ok(($($name::extract(map)?,)+))
+            }
+
+            fn openapi(
+                headers: &mut BTreeMap<String, utoipa::openapi::Header>,
+                _description: Option<&'static str>,
+            ) {
+                $($name::openapi(headers, None);)+
+            }
+        }
+    };
+}
+
+response_headers_tuple!(H1);
+response_headers_tuple!(H1, H2);
+response_headers_tuple!(H1, H2, H3);
+response_headers_tuple!(H1, H2, H3, H4);
+response_headers_tuple!(H1, H2, H3, H4, H5);
+response_headers_tuple!(H1, H2, H3, H4, H5, H6);
+response_headers_tuple!(H1, H2, H3, H4, H5, H6, H7);
+response_headers_tuple!(H1, H2, H3, H4, H5, H6, H7, H8);
+
+impl<T: IntoFrameworkResponse, H: ResponseHeaders> IntoFrameworkResponse for WithHeaders<T, H> {
+    fn into_framework_response(self, format: BodyFormat) -> Response {
+        let mut response = self.0.into_framework_response(format);
+        self.1.insert(response.headers_mut());
+        response
+    }
+
+    fn has_body() -> bool {
+        T::has_body()
+    }
+}
+
+impl<T: FromFrameworkResponse + Send, H: ResponseHeaders + Send> FromFrameworkResponse
+    for WithHeaders<T, H>
+{
+    async fn from_framework_response(
+        response: reqwest::Response,
+    ) -> Result<Self, ZelusClientError> {
+        let headers = H::extract(response.headers())?;
+        Ok(Self(T::from_framework_response(response).await?, headers))
+    }
+}
+
+impl<T: DocumentedResultResponse, H: ResponseHeaders> DocumentedResultResponse
+    for WithHeaders<T, H>
+{
+    fn openapi(
+        responses: ResponsesBuilder,
+        schemas: &mut HashMap<String, RefOr<Schema>>,
+    ) -> ResponsesBuilder {
+        let mut headers = BTreeMap::new();
+        H::openapi(&mut headers, None);
+
+        document_headers(
+            responses,
+            T::openapi(ResponsesBuilder::new(), schemas),
+            &headers,
+        )
+    }
+}
+
+pub(crate) fn document_headers(
+    mut responses: ResponsesBuilder,
+    documented: ResponsesBuilder,
+    headers: &BTreeMap<String, utoipa::openapi::Header>,
+) -> ResponsesBuilder {
+    for (code, response) in documented.build().responses {
+        responses = responses.response(
+            code,
+            match response {
+                RefOr::T(mut response) => {
+                    response.headers.extend(headers.clone());
+                    RefOr::T(response)
+                }
+                RefOr::Ref(reference) => RefOr::Ref(reference),
+            },
+        );
+    }
+    responses
+}
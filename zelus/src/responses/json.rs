@@ -1,4 +1,5 @@
 use crate::SUCCESS_DESCRIPTION;
+use crate::codec::BodyFormat;
 use crate::responses::DocumentedResultResponse;
 use crate::utils::MaybeUnit;
 use std::collections::HashMap;
@@ -21,18 +22,16 @@ impl<V: ToSchema + MaybeUnit + 'static> DocumentedResultResponse for V {
             vals.push((V::name().to_string(), V::schema()));
             V::schemas(&mut vals);
             schemas.extend(vals);
-            responses.response(
-                "200",
-                Response::builder()
-                    .description(SUCCESS_DESCRIPTION)
-                    .content(
-                        "application/json",
-                        Content::new(Some(
-                            RefBuilder::new().ref_location_from_schema_name(V::name()),
-                        )),
-                    )
-                    .build(),
-            )
+            let mut response = Response::builder().description(SUCCESS_DESCRIPTION);
+            for format in BodyFormat::ALL {
+                response = response.content(
+                    format.media_type(),
+                    Content::new(Some(
+                        RefBuilder::new().ref_location_from_schema_name(V::name()),
+                    )),
+                );
+            }
+            responses.response("200", response.build())
         }
     }
 }
@@ -1,13 +1,18 @@
 mod file;
+mod headers;
 mod json;
 mod redirect;
+mod status;
 mod ws;
 
 pub use file::FileResponse;
+pub use headers::{ResponseHeaders, WithHeaders};
 pub use redirect::Redirect;
+pub use status::{Accepted, Created, WithStatus};
 use std::collections::HashMap;
 use utoipa::openapi::{RefOr, ResponsesBuilder, Schema};
 pub use ws::WebsocketResponse;
+pub use zelus_macros::{ResponseHeaders, ZelusResponse};
 
 pub trait DocumentedResponse {
     fn openapi(
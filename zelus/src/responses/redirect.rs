@@ -1,49 +1,35 @@
+use crate::codec::BodyFormat;
+use crate::internal::{FromFrameworkResponse, IntoFrameworkResponse};
 use crate::responses::DocumentedResultResponse;
+use crate::sdk::ZelusClientError;
 use axum::response::IntoResponse;
 use http::header::LOCATION;
 use http::{HeaderValue, StatusCode};
 use std::collections::HashMap;
-use utoipa::openapi::{RefOr, Response, ResponsesBuilder, Schema};
+use url::Url;
+use utoipa::openapi::header::HeaderBuilder;
+use utoipa::openapi::{Object, RefOr, Response, ResponsesBuilder, Schema, Type};
 
 #[must_use]
-pub struct Redirect<const CODE: u16>(axum::response::Redirect);
+pub struct Redirect<const CODE: u16>(HeaderValue, bool);
 
 trait RedirectStatusCode {
     const STATUS: StatusCode;
-
-    fn new_internal(uri: &str) -> axum::response::Redirect
-    where
-        Self: Sized;
+}
+impl RedirectStatusCode for Redirect<301> {
+    const STATUS: StatusCode = StatusCode::MOVED_PERMANENTLY;
+}
+impl RedirectStatusCode for Redirect<302> {
+    const STATUS: StatusCode = StatusCode::FOUND;
 }
 impl RedirectStatusCode for Redirect<303> {
     const STATUS: StatusCode = StatusCode::SEE_OTHER;
-
-    fn new_internal(uri: &str) -> axum::response::Redirect
-    where
-        Self: Sized,
-    {
-        axum::response::Redirect::to(uri)
-    }
 }
 impl RedirectStatusCode for Redirect<307> {
     const STATUS: StatusCode = StatusCode::TEMPORARY_REDIRECT;
-
-    fn new_internal(uri: &str) -> axum::response::Redirect
-    where
-        Self: Sized,
-    {
-        axum::response::Redirect::temporary(uri)
-    }
 }
 impl RedirectStatusCode for Redirect<308> {
     const STATUS: StatusCode = StatusCode::PERMANENT_REDIRECT;
-
-    fn new_internal(uri: &str) -> axum::response::Redirect
-    where
-        Self: Sized,
-    {
-        axum::response::Redirect::permanent(uri)
-    }
 }
 
 impl<const CODE: u16> DocumentedResultResponse for Redirect<CODE>
@@ -62,6 +48,13 @@ where
                         .canonical_reason()
                         .unwrap_or_else(|| Self::STATUS.as_str()),
                 )
+                .header(
+                    LOCATION.as_str(),
+                    HeaderBuilder::new()
+                        .schema(Schema::Object(Object::with_type(Type::String)))
+                        .description(Some("Target of the redirect"))
+                        .build(),
+                )
                 .build(),
         )
     }
@@ -72,16 +65,44 @@ where
     Self: RedirectStatusCode,
 {
     pub fn new(uri: &str) -> Self {
-        Self(Self::new_internal(uri))
+        Self(
+            HeaderValue::try_from(uri).expect("URI isn't a valid header value"),
+            false,
+        )
+    }
+
+    /// Redirects to an url, like the ones generated for the routes of a service.
+    pub fn to_url(url: &Url) -> Self {
+        Self::new(url.as_str())
     }
 
     pub fn into_uri(self) -> HeaderValue {
         self.0
-            .into_response()
-            .headers()
-            .get(LOCATION)
-            .cloned()
-            .expect("axum::response::Redirect should always have a location header")
+    }
+
+    /// Whether the client already followed the redirect, the target is then the url of the
+    /// final response instead of the `Location` header.
+    #[must_use]
+    pub const fn followed(&self) -> bool {
+        self.1
+    }
+
+    /// Parses the target of the redirect.
+    ///
+    /// Redirects received by the SDK always contain an absolute url.
+    pub fn url(&self) -> Result<Url, url::ParseError> {
+        Url::parse(&String::from_utf8_lossy(self.0.as_bytes()))
+    }
+
+    /// Follows the redirect with a GET request.
+    pub async fn follow(
+        &self,
+        client: &reqwest::Client,
+    ) -> Result<reqwest::Response, ZelusClientError> {
+        Ok(client
+            .get(self.url().map_err(ZelusClientError::Location)?)
+            .send()
+            .await?)
     }
 }
 
@@ -90,6 +111,75 @@ where
     Self: RedirectStatusCode,
 {
     fn into_response(self) -> axum::response::Response {
-        self.0.into_response()
+        (Self::STATUS, [(LOCATION, self.0)]).into_response()
+    }
+}
+
+impl<const CODE: u16> IntoFrameworkResponse for Redirect<CODE>
+where
+    Self: RedirectStatusCode,
+{
+    fn into_framework_response(self, _format: BodyFormat) -> axum::response::Response {
+        self.into_response()
+    }
+
+    fn has_body() -> bool {
+        false
+    }
+}
+
+impl<const CODE: u16> Redirect<CODE>
+where
+    Self: RedirectStatusCode,
+{
+    fn received(response: &reqwest::Response) -> Option<Self> {
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .filter(|_location| response.status() == Self::STATUS)?;
+        let target = response.url().join(location.to_str().ok()?).ok()?;
+        Some(Self(
+            HeaderValue::try_from(target.as_str()).expect("Url is always a valid header value"),
+            false,
+        ))
+    }
+
+    fn followed_to(response: &reqwest::Response) -> Self {
+        Self(
+            HeaderValue::try_from(response.url().as_str())
+                .expect("Url is always a valid header value"),
+            true,
+        )
+    }
+}
+
+/// Uses the `Location` header of the redirect, otherwise the url of the final response
+/// as a [followed](Redirect::followed) redirect.
+impl<const CODE: u16> From<reqwest::Response> for Redirect<CODE>
+where
+    Self: RedirectStatusCode,
+{
+    fn from(value: reqwest::Response) -> Self {
+        Self::received(&value).unwrap_or_else(|| Self::followed_to(&value))
+    }
+}
+
+/// Fails on other redirects and redirects without a valid `Location` header.
+impl<const CODE: u16> FromFrameworkResponse for Redirect<CODE>
+where
+    Self: RedirectStatusCode,
+{
+    const REDIRECT: bool = true;
+
+    async fn from_framework_response(
+        response: reqwest::Response,
+    ) -> Result<Self, ZelusClientError> {
+        if response.status() == Self::STATUS {
+            Self::received(&response).ok_or(ZelusClientError::Header(&LOCATION))
+        } else if response.status().is_redirection() {
+            Err(ZelusClientError::Status(response.status()))
+        } else {
+            Ok(Self::followed_to(&response))
+        }
     }
 }
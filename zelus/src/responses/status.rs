@@ -0,0 +1,133 @@
+use crate::codec::BodyFormat;
+use crate::internal::{FromFrameworkResponse, IntoFrameworkResponse, document_status};
+use crate::responses::DocumentedResultResponse;
+use crate::responses::headers::document_headers;
+use crate::sdk::ZelusClientError;
+use axum::response::Response;
+use http::header::InvalidHeaderValue;
+use http::header::LOCATION;
+use http::{HeaderValue, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::openapi::header::HeaderBuilder;
+use utoipa::openapi::{Object, RefOr, ResponsesBuilder, Schema, Type};
+
+/// Success response answering with `CODE` instead of `200 OK`, any 2xx status code.
+pub struct WithStatus<const CODE: u16, T>(pub T);
+
+/// `202 Accepted` response.
+pub type Accepted<T> = WithStatus<202, T>;
+
+/// `201 Created` response with an optional `Location` header pointing to the created resource.
+pub struct Created<T>(pub T, pub Option<HeaderValue>);
+
+impl<const CODE: u16, T> WithStatus<CODE, T> {
+    const SUCCESS: () = assert!(
+        CODE >= 200 && CODE < 300,
+        "WithStatus only takes 2xx status codes"
+    );
+
+    fn status() -> StatusCode {
+        let () = Self::SUCCESS;
+        StatusCode::from_u16(CODE).expect("2xx status codes are valid")
+    }
+}
+
+impl<const CODE: u16, T: IntoFrameworkResponse> IntoFrameworkResponse for WithStatus<CODE, T> {
+    fn into_framework_response(self, format: BodyFormat) -> Response {
+        let mut response = self.0.into_framework_response(format);
+        // Failing to serialize the body answers with an error status, which is kept
+        if response.status().is_success() {
+            *response.status_mut() = Self::status();
+        }
+        response
+    }
+
+    fn has_body() -> bool {
+        T::has_body()
+    }
+}
+
+impl<const CODE: u16, T: FromFrameworkResponse + Send> FromFrameworkResponse
+    for WithStatus<CODE, T>
+{
+    async fn from_framework_response(
+        response: reqwest::Response,
+    ) -> Result<Self, ZelusClientError> {
+        let () = Self::SUCCESS;
+        T::from_framework_response(response).await.map(Self)
+    }
+}
+
+impl<const CODE: u16, T: DocumentedResultResponse> DocumentedResultResponse
+    for WithStatus<CODE, T>
+{
+    fn openapi(
+        responses: ResponsesBuilder,
+        schemas: &mut HashMap<String, RefOr<Schema>>,
+    ) -> ResponsesBuilder {
+        document_status(
+            responses,
+            T::openapi(ResponsesBuilder::new(), schemas),
+            Self::status(),
+            None,
+        )
+    }
+}
+
+impl<T> Created<T> {
+    #[must_use]
+    pub const fn new(body: T) -> Self {
+        Self(body, None)
+    }
+
+    /// Fails if `uri` isn't a valid header value.
+    pub fn located(body: T, uri: &str) -> Result<Self, InvalidHeaderValue> {
+        Ok(Self(body, Some(HeaderValue::try_from(uri)?)))
+    }
+}
+
+impl<T: IntoFrameworkResponse> IntoFrameworkResponse for Created<T> {
+    fn into_framework_response(self, format: BodyFormat) -> Response {
+        let mut response = WithStatus::<201, T>(self.0).into_framework_response(format);
+        if response.status().is_success()
+            && let Some(location) = self.1
+        {
+            response.headers_mut().insert(LOCATION, location);
+        }
+        response
+    }
+
+    fn has_body() -> bool {
+        T::has_body()
+    }
+}
+
+impl<T: FromFrameworkResponse + Send> FromFrameworkResponse for Created<T> {
+    async fn from_framework_response(
+        response: reqwest::Response,
+    ) -> Result<Self, ZelusClientError> {
+        let location = response.headers().get(LOCATION).cloned();
+        Ok(Self(T::from_framework_response(response).await?, location))
+    }
+}
+
+impl<T: DocumentedResultResponse> DocumentedResultResponse for Created<T> {
+    fn openapi(
+        responses: ResponsesBuilder,
+        schemas: &mut HashMap<String, RefOr<Schema>>,
+    ) -> ResponsesBuilder {
+        let mut headers = BTreeMap::new();
+        headers.insert(
+            LOCATION.as_str().to_owned(),
+            HeaderBuilder::new()
+                .schema(Schema::Object(Object::with_type(Type::String)))
+                .description(Some("Location of the created resource"))
+                .build(),
+        );
+        document_headers(
+            responses,
+            WithStatus::<201, T>::openapi(ResponsesBuilder::new(), schemas),
+            &headers,
+        )
+    }
+}
@@ -1,11 +1,19 @@
+use crate::cors::{CorsPolicy, cors_response, join, preflight};
 use axum::extract::Request;
 use axum::response::IntoResponse;
 use axum::routing::{MethodRouter, Route};
 use core::convert::Infallible;
+use http::header::{ALLOW, AUTHORIZATION, CONTENT_TYPE};
+use http::{HeaderMap, HeaderName, StatusCode};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
 use tower_layer::Layer;
 use tower_service::Service;
-use utoipa::openapi::path::OperationBuilder;
-use utoipa::openapi::{HttpMethod, OpenApi, Paths, RefOr, ResponsesBuilder, Schema};
+use utoipa::openapi::header::HeaderBuilder;
+use utoipa::openapi::path::{Operation, OperationBuilder, ParameterIn};
+use utoipa::openapi::{
+    HttpMethod, Object, OpenApi, Paths, RefOr, Response, ResponsesBuilder, Schema, Type,
+};
 use utoipa_axum::router::OpenApiRouter;
 
 #[must_use]
@@ -17,17 +25,38 @@ enum RouterOperation {
     Route(Box<ZelusRoute>),
     Merge(ZelusRouter),
     Patch(Box<dyn FnOnce(OpenApiRouter) -> OpenApiRouter + Send + Sync>),
+    AutomaticMethods(bool, Option<Arc<CorsPolicy>>),
+    #[cfg(feature = "error")]
+    OnError(Arc<crate::error::context::ErrorReporter>),
+}
+
+impl RouterOperation {
+    const fn is_error_hook(&self) -> bool {
+        #[cfg(feature = "error")]
+        if let Self::OnError(_reporter) = self {
+            return true;
+        }
+        false
+    }
 }
 
 struct ZelusRoute {
     path: &'static str,
     method: HttpMethod,
+    request_headers: BTreeSet<String>,
     responses: ResponsesBuilder,
     operations: OperationBuilder,
     schemas: Vec<(String, RefOr<Schema>)>,
     router: MethodRouter,
 }
 
+/// Methods and request headers of the routes sharing a path.
+#[derive(Default)]
+struct AllowedPath {
+    methods: BTreeSet<HttpMethod>,
+    headers: BTreeSet<String>,
+}
+
 impl ZelusRouter {
     pub const fn new() -> Self {
         Self(None, Vec::new())
@@ -93,6 +122,11 @@ impl ZelusRouter {
                     RouterOperation::Merge(router.document_middleware0(func))
                 }
                 RouterOperation::Patch(func) => RouterOperation::Patch(func),
+                RouterOperation::AutomaticMethods(documented, cors) => {
+                    RouterOperation::AutomaticMethods(documented, cors)
+                }
+                #[cfg(feature = "error")]
+                RouterOperation::OnError(reporter) => RouterOperation::OnError(reporter),
             })
             .collect();
         self
@@ -112,7 +146,163 @@ impl ZelusRouter {
         self
     }
 
+    /// Compresses responses according to `Accept-Encoding` and decompresses
+    /// request bodies according to `Content-Encoding`.
+    ///
+    /// The encodings are enabled by the `gzip`, `brotli` and `zstd` features, which also
+    /// enable them in the SDK. Like [`Self::layer`], it only applies to routes added before.
+    /// `#[service(compression)]` applies it to the routes of a service.
+    #[cfg(any(feature = "gzip", feature = "brotli", feature = "zstd"))]
+    pub fn compression(self) -> Self {
+        self.layer(tower_http::compression::CompressionLayer::new())
+            .layer(tower_http::decompression::RequestDecompressionLayer::new())
+    }
+
+    /// Answers `OPTIONS` with the `Allow` header computed from the route table.
+    ///
+    /// `HEAD` is always answered for `GET` routes by running the handler and dropping the body.
+    /// If `documented` is set, both are added to the documentation.
+    /// Like [`Self::layer`], it only applies to routes added before.
+    pub fn automatic_methods(mut self, documented: bool) -> Self {
+        // Paths answered by an earlier call or by `cors` get documented as well
+        for op in &mut self.1 {
+            if let RouterOperation::AutomaticMethods(other_documented, _cors) = op {
+                *other_documented |= documented;
+            }
+        }
+        self.1
+            .push(RouterOperation::AutomaticMethods(documented, None));
+        self
+    }
+
+    /// Applies a cross-origin policy to the routes added before.
+    ///
+    /// Preflight requests are answered like with [`Self::automatic_methods`], allowing the methods
+    /// and request headers the routes of the path use: header arguments, `Content-Type` for
+    /// payloads and `Authorization` for authenticated routes.
+    pub fn cors(mut self, policy: CorsPolicy) -> Self {
+        let policy = Arc::new(policy);
+        // Paths already answered by `automatic_methods` answer preflight requests as well
+        for op in &mut self.1 {
+            if let RouterOperation::AutomaticMethods(_documented, cors @ None) = op {
+                *cors = Some(Arc::clone(&policy));
+            }
+        }
+        self.1.push(RouterOperation::AutomaticMethods(
+            false,
+            Some(Arc::clone(&policy)),
+        ));
+        self.layer(axum::middleware::from_fn_with_state(policy, cors_response))
+    }
+
+    /// Translates the messages of error responses according to `Accept-Language`.
+    ///
+    /// Like [`Self::layer`], it only applies to routes added before.
+    #[cfg(feature = "error")]
+    pub fn localize(self, catalog: crate::locale::MessageCatalog) -> Self {
+        self.layer(axum::middleware::from_fn_with_state(
+            Arc::new(catalog),
+            crate::locale::localize_errors,
+        ))
+    }
+
+    /// Renders error responses in another body format, like RFC 9457 problem+json with
+    /// [`ProblemJson`](crate::error::format::ProblemJson), and documents them accordingly.
+    ///
+    /// Applied to the top-level router it configures all services, otherwise only the services
+    /// nested or merged into this one. Like [`Self::layer`], it only applies to routes added before.
+    #[cfg(feature = "error")]
+    pub fn error_format(mut self, format: impl crate::error::format::ErrorFormat) -> Self {
+        let format: Arc<dyn crate::error::format::ErrorFormat> = Arc::new(format);
+        let documented = Arc::clone(&format);
+        self.1
+            .push(RouterOperation::Patch(Box::new(move |mut router| {
+                crate::error::format::document_format(router.get_openapi_mut(), &*documented);
+                router
+            })));
+        self.layer(axum::middleware::from_fn_with_state(
+            format,
+            crate::error::format::format_errors,
+        ))
+    }
+
+    /// Calls `hook` with every error response of the routes added before, like [`Self::layer`],
+    /// for logging, metrics or alerting.
+    #[cfg(feature = "error")]
+    pub fn on_error(
+        self,
+        hook: impl Fn(&crate::error::context::ErrorContext<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_error_with_request_id(HeaderName::from_static("x-request-id"), hook)
+    }
+
+    /// Like [`Self::on_error`], with the request id read from the header `request_id`.
+    #[cfg(feature = "error")]
+    pub fn on_error_with_request_id(
+        mut self,
+        request_id: HeaderName,
+        hook: impl Fn(&crate::error::context::ErrorContext<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.1.push(RouterOperation::OnError(Arc::new(
+            crate::error::context::ErrorReporter {
+                hook: Box::new(hook),
+                request_id,
+            },
+        )));
+        self
+    }
+
+    /// Adds the catalog of the errors the routes added before can return to the documentation,
+    /// as the `x-error-catalog` extension.
+    ///
+    /// Ids returned with different status codes, like the same category defined twice with
+    /// `define_error!`, are logged as warnings.
+    ///
+    /// The catalog itself is available with [`ErrorCatalog::from_openapi`](crate::error::catalog::ErrorCatalog::from_openapi).
+    #[cfg(feature = "error")]
+    pub fn error_catalog(mut self) -> Self {
+        self.1.push(RouterOperation::Patch(Box::new(|mut router| {
+            let openapi = router.get_openapi_mut();
+            let catalog = crate::error::catalog::ErrorCatalog::from_openapi(openapi);
+            openapi
+                .extensions
+                .get_or_insert_default()
+                .insert("x-error-catalog".to_owned(), catalog.to_json());
+            router
+        })));
+        self
+    }
+
     pub fn route(
+        &mut self,
+        path: &'static str,
+        method: HttpMethod,
+        documentation: (
+            ResponsesBuilder,
+            OperationBuilder,
+            Vec<(String, RefOr<Schema>)>,
+        ),
+        router: MethodRouter,
+    ) -> &mut Self {
+        self.push_route(path, method, documentation, router, false)
+    }
+
+    /// Adds a route requiring the `Authorization` header.
+    pub fn authenticated_route(
+        &mut self,
+        path: &'static str,
+        method: HttpMethod,
+        documentation: (
+            ResponsesBuilder,
+            OperationBuilder,
+            Vec<(String, RefOr<Schema>)>,
+        ),
+        router: MethodRouter,
+    ) -> &mut Self {
+        self.push_route(path, method, documentation, router, true)
+    }
+
+    fn push_route(
         &mut self,
         path: &'static str,
         method: HttpMethod,
@@ -122,12 +312,28 @@ impl ZelusRouter {
             Vec<(String, RefOr<Schema>)>,
         ),
         router: MethodRouter,
+        authenticated: bool,
     ) -> &mut Self {
+        let operation: Operation = operations.build();
+        let mut request_headers = operation
+            .parameters
+            .iter()
+            .flatten()
+            .filter(|parameter| parameter.parameter_in == ParameterIn::Header)
+            .map(|parameter| parameter.name.to_ascii_lowercase())
+            .collect::<BTreeSet<_>>();
+        if operation.request_body.is_some() {
+            request_headers.insert(CONTENT_TYPE.as_str().to_owned());
+        }
+        if authenticated {
+            request_headers.insert(AUTHORIZATION.as_str().to_owned());
+        }
         self.1.push(RouterOperation::Route(Box::new(ZelusRoute {
             path,
             method,
+            request_headers,
             responses,
-            operations,
+            operations: operation.into(),
             schemas,
             router,
         })));
@@ -146,47 +352,216 @@ impl ZelusRouter {
 
     #[must_use]
     pub fn into_openapi(self) -> OpenApiRouter {
+        self.into_openapi0(false)
+    }
+
+    /// `hooked` is set if the routes are wrapped by an `on_error` hook of a parent router.
+    fn into_openapi0(self, hooked: bool) -> OpenApiRouter {
         let mut openapi = self
             .0
             .map_or_else(OpenApiRouter::new, OpenApiRouter::with_openapi);
+        let mut allowed = BTreeMap::new();
+
+        // Only the routes before a hook need to attach their operation id to errors
+        let mut hooks = self.1.iter().filter(|op| op.is_error_hook()).count();
 
         for op in self.1 {
+            let hooked = hooked || hooks > 0;
             match op {
                 RouterOperation::Nest(path, router) => {
-                    openapi = openapi.nest(path, router.into_openapi());
+                    extend_allowed(&mut allowed, path, router.allowed_methods());
+                    openapi = openapi.nest(path, router.into_openapi0(hooked));
                 }
                 RouterOperation::Route(route) => {
                     let ZelusRoute {
                         path,
                         method,
+                        request_headers,
                         responses,
                         operations,
                         schemas,
                         router,
                     } = *route;
+                    let allowed_path: &mut AllowedPath =
+                        allowed.entry(path.to_owned()).or_default();
+                    allowed_path.methods.insert(method.clone());
+                    allowed_path.headers.extend(request_headers);
+                    let operation = operations.responses(responses).build();
+                    #[cfg(feature = "error")]
+                    let router = if hooked {
+                        crate::error::context::tag_operation(
+                            router,
+                            operation.operation_id.as_deref(),
+                        )
+                    } else {
+                        router
+                    };
                     openapi = openapi.routes((
                         schemas,
                         {
                             let mut paths = Paths::new();
-                            paths.add_path_operation(
-                                path,
-                                vec![method],
-                                operations.responses(responses).build(),
-                            );
+                            paths.add_path_operation(path, vec![method], operation);
                             paths
                         },
                         router,
                     ));
                 }
                 RouterOperation::Merge(other) => {
-                    openapi = openapi.merge(other.into_openapi());
+                    extend_allowed(&mut allowed, "/", other.allowed_methods());
+                    openapi = openapi.merge(other.into_openapi0(hooked));
                 }
                 RouterOperation::Patch(func) => {
                     openapi = func(openapi);
                 }
+                RouterOperation::AutomaticMethods(documented, cors) => {
+                    for (path, allowed_path) in &mut allowed {
+                        if allowed_path.methods.insert(HttpMethod::Options) {
+                            openapi = automatic_methods(
+                                openapi,
+                                path,
+                                allowed_path,
+                                documented,
+                                cors.clone(),
+                            );
+                        }
+                    }
+                }
+                #[cfg(feature = "error")]
+                RouterOperation::OnError(reporter) => {
+                    hooks -= 1;
+                    openapi = openapi.layer(axum::middleware::from_fn_with_state(
+                        reporter,
+                        crate::error::context::report_errors,
+                    ));
+                }
             }
         }
 
         openapi
     }
+
+    fn allowed_methods(&self) -> BTreeMap<String, AllowedPath> {
+        let mut allowed = BTreeMap::new();
+        for op in &self.1 {
+            match op {
+                RouterOperation::Nest(path, router) => {
+                    extend_allowed(&mut allowed, path, router.allowed_methods());
+                }
+                RouterOperation::Route(route) => {
+                    let allowed_path: &mut AllowedPath =
+                        allowed.entry(route.path.to_owned()).or_default();
+                    allowed_path.methods.insert(route.method.clone());
+                    allowed_path
+                        .headers
+                        .extend(route.request_headers.iter().cloned());
+                }
+                RouterOperation::Merge(other) => {
+                    extend_allowed(&mut allowed, "/", other.allowed_methods());
+                }
+                RouterOperation::Patch(_func) => {}
+                RouterOperation::AutomaticMethods(_documented, _cors) => {
+                    for allowed_path in allowed.values_mut() {
+                        allowed_path.methods.insert(HttpMethod::Options);
+                    }
+                }
+                #[cfg(feature = "error")]
+                RouterOperation::OnError(_reporter) => {}
+            }
+        }
+        allowed
+    }
+}
+
+/// Adds the paths of a nested router, prefixing them the same way axum does.
+fn extend_allowed(
+    allowed: &mut BTreeMap<String, AllowedPath>,
+    prefix: &str,
+    nested: BTreeMap<String, AllowedPath>,
+) {
+    for (path, nested_path) in nested {
+        let path = match (prefix, path.as_str()) {
+            ("/", path) => path.to_owned(),
+            (prefix, "/") => prefix.to_owned(),
+            (prefix, path) => format!("{}{path}", prefix.trim_end_matches('/')),
+        };
+        let allowed_path = allowed.entry(path).or_default();
+        allowed_path.methods.extend(nested_path.methods);
+        allowed_path.headers.extend(nested_path.headers);
+    }
+}
+
+const fn method_name(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "GET",
+        HttpMethod::Post => "POST",
+        HttpMethod::Put => "PUT",
+        HttpMethod::Delete => "DELETE",
+        HttpMethod::Options => "OPTIONS",
+        HttpMethod::Head => "HEAD",
+        HttpMethod::Patch => "PATCH",
+        HttpMethod::Trace => "TRACE",
+    }
+}
+
+fn automatic_methods(
+    mut openapi: OpenApiRouter,
+    path: &str,
+    AllowedPath { methods, headers }: &AllowedPath,
+    documented: bool,
+    cors: Option<Arc<CorsPolicy>>,
+) -> OpenApiRouter {
+    let mut allow = methods.iter().map(method_name).collect::<Vec<_>>();
+    if methods.contains(&HttpMethod::Get) && !methods.contains(&HttpMethod::Head) {
+        allow.push(method_name(&HttpMethod::Head));
+    }
+    let allow = join(allow).expect("Paths have at least one method");
+    let allow_headers = join(headers.iter().map(String::as_str));
+
+    if documented {
+        let paths = &mut openapi.get_openapi_mut().paths;
+        let head = paths
+            .paths
+            .get(path)
+            .filter(|_item| !methods.contains(&HttpMethod::Head))
+            .and_then(|item| item.get.clone());
+        if let Some(mut head) = head {
+            head.operation_id = head
+                .operation_id
+                .map(|operation_id| format!("{operation_id}_head"));
+            for response in head.responses.responses.values_mut() {
+                if let RefOr::T(response) = response {
+                    response.content.clear();
+                }
+            }
+            paths.add_path_operation(path, vec![HttpMethod::Head], head);
+        }
+        paths.add_path_operation(
+            path,
+            vec![HttpMethod::Options],
+            OperationBuilder::new()
+                .responses(
+                    ResponsesBuilder::new().response(
+                        StatusCode::NO_CONTENT.as_str(),
+                        Response::builder()
+                            .description("Allowed methods")
+                            .header(
+                                ALLOW.as_str(),
+                                HeaderBuilder::new()
+                                    .schema(Schema::Object(Object::with_type(Type::String)))
+                                    .description(Some("Methods allowed for this path"))
+                                    .build(),
+                            )
+                            .build(),
+                    ),
+                )
+                .build(),
+        );
+    }
+
+    openapi.route(
+        path,
+        MethodRouter::new().options(async move |headers: HeaderMap| {
+            preflight(cors.as_deref(), &headers, allow, allow_headers)
+        }),
+    )
 }
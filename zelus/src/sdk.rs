@@ -1,6 +1,65 @@
+use crate::codec::{BodyFormat, CodecError};
+use http::{HeaderName, StatusCode};
+use thiserror::Error;
 use url::Url;
 
 pub trait ZelusClientImpl {
     fn base_url(&self) -> &Url;
     fn client(&self) -> &reqwest::Client;
+
+    /// Client for routes answering with redirects, configured like [`Self::client`] but built
+    /// with [`reqwest::redirect::Policy::none`], as the redirects are part of their responses.
+    ///
+    /// Clients of services without such routes can return [`Self::client`].
+    fn redirect_client(&self) -> &reqwest::Client;
+
+    /// Format used for payloads and preferred for responses.
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::default()
+    }
+}
+
+/// Error of a request of the SDK, held by the `Communication` variant of generated errors.
+///
+/// `Communication` used to hold the [`reqwest::Error`] itself, which is now
+/// [`Self::Reqwest`]. `From<reqwest::Error>` is still implemented by generated errors.
+#[derive(Debug, Error)]
+pub enum ZelusClientError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error("Missing or invalid response header {0}")]
+    Header(&'static HeaderName),
+    #[error(transparent)]
+    Codec(#[from] CodecError),
+    #[error("Unexpected response status {0}")]
+    Status(StatusCode),
+    #[error("Invalid redirect target: {0}")]
+    Location(url::ParseError),
+}
+
+impl ZelusClientError {
+    /// The status of the error response, if the request got one.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Reqwest(err) => err.status(),
+            Self::Status(status) => Some(*status),
+            Self::Header(_) | Self::Codec(_) | Self::Location(_) => None,
+        }
+    }
+
+    /// Whether the request may succeed if repeated, for timeouts, failed connections and
+    /// responses of overloaded servers or gateways.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Reqwest(err) => {
+                err.is_timeout()
+                    || err.is_connect()
+                    || err.status().is_some_and(crate::internal::retryable_status)
+            }
+            Self::Status(status) => crate::internal::retryable_status(*status),
+            Self::Header(_) | Self::Codec(_) | Self::Location(_) => false,
+        }
+    }
 }
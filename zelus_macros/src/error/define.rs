@@ -1,9 +1,11 @@
 // SPDX-License-Identifier: AGPL-3.0-only
+use either::Either;
 use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
 use quote::quote;
 use std::collections::HashMap;
 use syn::parse::{Parse, ParseStream};
-use syn::{Error, LitStr, Token};
+use syn::token::Brace;
+use syn::{Attribute, Error, Expr, ExprLit, FieldsNamed, Lit, LitInt, LitStr, Meta, Token};
 
 pub struct ErrorDefinitionContent {
     pub map: HashMap<Ident, ErrorDefinitionInner>,
@@ -12,16 +14,24 @@ pub struct ErrorDefinitionContent {
 impl Parse for ErrorDefinitionContent {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut map = HashMap::new();
-        for (name, description, statuscode) in
-            input.parse_terminated(parse_error_content, Token![,])?
-        {
-            map.insert(
-                name,
-                ErrorDefinitionInner {
-                    description,
-                    statuscode,
-                },
-            );
+        let mut codes = HashMap::new();
+        for (name, inner) in input.parse_terminated(parse_error_content, Token![,])? {
+            if map.contains_key(&name) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("Duplicate error {name} in this category"),
+                ));
+            }
+            if let Some(code) = &inner.code {
+                let value = code.base10_parse::<u32>()?;
+                if let Some(other) = codes.insert(value, name.clone()) {
+                    return Err(Error::new(
+                        code.span(),
+                        format!("The errors {other} and {name} both use the code {value}"),
+                    ));
+                }
+            }
+            map.insert(name, inner);
         }
         Ok(Self { map })
     }
@@ -30,6 +40,19 @@ impl Parse for ErrorDefinitionContent {
 pub struct ErrorDefinitionInner {
     pub description: LitStr,
     pub statuscode: Ident,
+    pub details: Option<FieldsNamed>,
+    /// Numeric code of the error, like `code = 1001`.
+    pub code: Option<LitInt>,
+    /// Link to the documentation of the error, like `help_url = "https://.."`.
+    pub help_url: Option<LitStr>,
+    /// Whether the request may be repeated later, like `retryable`.
+    pub retryable: bool,
+    /// Seconds to wait before repeating the request, or a function computing them from the
+    /// details when the response is created, like `retry_after = 30`,
+    /// `retry_after = retry_after_quota` or `retry_after = |details| ..`.
+    pub retry_after: Option<Either<LitInt, Expr>>,
+    /// Lines of the `///` comments of the error.
+    pub doc: Vec<LitStr>,
 }
 
 impl Parse for ErrorDefinitionInner {
@@ -38,44 +61,163 @@ impl Parse for ErrorDefinitionInner {
 
         let statuscode = input.parse()?;
 
+        let mut details = None;
+        let mut code = None;
+        let mut help_url = None;
+        let mut retryable = false;
+        let mut retry_after = None;
+        while !input.is_empty() {
+            if input.peek(Brace) && details.is_none() {
+                details = Some(input.parse()?);
+                continue;
+            }
+            let key: Ident = input.parse()?;
+            if key == "retryable" && !input.peek(Token![=]) {
+                if retryable {
+                    return Err(Error::new(key.span(), "Duplicate retryable in error"));
+                }
+                retryable = true;
+                continue;
+            }
+            let _: Token![=] = input.parse()?;
+            match key.to_string().as_str() {
+                "code" if code.is_none() => {
+                    let value: LitInt = input.parse()?;
+                    value.base10_parse::<u32>()?;
+                    code = Some(value);
+                }
+                "help_url" if help_url.is_none() => help_url = Some(input.parse()?),
+                "retry_after" if retry_after.is_none() => {
+                    retry_after = Some(if input.peek(LitInt) {
+                        let value: LitInt = input.parse()?;
+                        value.base10_parse::<u64>()?;
+                        Either::Left(value)
+                    } else {
+                        Either::Right(input.call(Expr::parse_without_eager_brace)?)
+                    });
+                }
+                "code" | "help_url" | "retry_after" => {
+                    return Err(Error::new(key.span(), format!("Duplicate {key} in error")));
+                }
+                _ => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!(
+                            "Unknown field {key} in error, expected code, help_url, retryable or retry_after"
+                        ),
+                    ));
+                }
+            }
+        }
+
         Ok(Self {
             description,
             statuscode,
+            details,
+            code,
+            help_url,
+            // Waiting for the request to be repeated only makes sense if it can succeed
+            retryable: retryable || retry_after.is_some(),
+            retry_after,
+            doc: Vec::new(),
         })
     }
 }
 
-pub fn define_error0(crate_prefix: &TokenStream, attr: TokenStream) -> TokenStream {
+pub fn define_error0(crate_prefix: &TokenStream, attr: TokenStream) -> syn::Result<TokenStream> {
     let mut result = Vec::new();
 
     let mut attr = attr.into_iter();
 
-    let Some(TokenTree::Ident(category)) = attr.next() else {
-        panic!("Expected identifier");
+    let category = match attr.next() {
+        Some(TokenTree::Ident(category)) => category,
+        token => return Err(Error::new_spanned(token, "Expected identifier")),
     };
 
-    let Some(TokenTree::Group(content)) = attr.next() else {
-        panic!("Expected braces");
+    let content = match attr.next() {
+        Some(TokenTree::Group(content)) if content.delimiter() == Delimiter::Brace => content,
+        token => return Err(Error::new_spanned(token, "Expected braces")),
     };
-    assert_eq!(content.delimiter(), Delimiter::Brace, "Expected braces");
-    let ErrorDefinitionContent { map } =
-        syn::parse2(content.stream()).expect("Expected error content");
+    if let Some(token) = attr.next() {
+        return Err(Error::new_spanned(
+            token,
+            "Unexpected token after the errors",
+        ));
+    }
+    let ErrorDefinitionContent { map } = syn::parse2(content.stream())?;
 
     for (error, inner) in map {
         let statuscode = inner.statuscode;
         let description = inner.description;
+        let code = option(inner.code.map(|code| quote! { #code }));
+        let help_url = option(inner.help_url.map(|help_url| quote! { #help_url }));
+        let retryable = inner.retryable;
+        let doc = &inner.doc;
+        let documentation = option((!doc.is_empty()).then(|| {
+            let documentation = doc
+                .iter()
+                .map(|line| {
+                    let line = line.value();
+                    line.strip_prefix(' ').unwrap_or(&line).to_owned()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            quote! { #documentation }
+        }));
+
+        // Errors with details compute the duration from them
+        let (retry_after_args, retry_after_type) = if inner.details.is_some() {
+            (
+                quote! { |_details| },
+                quote! { fn(&[< #category:camel #error:camel Details >]) -> std::time::Duration },
+            )
+        } else {
+            (quote! { || }, quote! { fn() -> std::time::Duration })
+        };
+        let retry_after = option(inner.retry_after.map(|retry_after| match retry_after {
+            Either::Left(seconds) => {
+                quote! { #retry_after_args std::time::Duration::from_secs(#seconds) }
+            }
+            Either::Right(function) => quote! { #function },
+        }));
+
+        let (details, from_args) = if let Some(mut fields) = inner.details {
+            for field in &mut fields.named {
+                field.vis = syn::parse_quote!(pub);
+            }
+            (
+                quote! {
+                    /// Details of the error, serialized under `error.details`.
+                    #[derive(Clone, #crate_prefix serde::Serialize, #crate_prefix serde::Deserialize, Debug, #crate_prefix utoipa::ToSchema)]
+                    pub struct [< #category:camel #error:camel Details >] #fields
+                },
+                quote! { details: [< #category:camel #error:camel Details >] },
+            )
+        } else {
+            (TokenStream::new(), TokenStream::new())
+        };
 
         result.extend(quote! {
             #crate_prefix paste! {
+                #( #[doc = #doc] )*
                 #[automatically_derived]
                 pub mod [< error_ #category:snake _ #error:snake >] {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #details
 
                     pub const STATUSCODE: #crate_prefix http::StatusCode = #crate_prefix http::StatusCode::#statuscode;
                     pub const DESCRIPTION: &str = #description;
+                    pub const CODE: Option<u32> = #code;
+                    pub const HELP_URL: Option<&str> = #help_url;
+                    pub const DOCUMENTATION: Option<&str> = #documentation;
+                    pub const RETRYABLE: bool = #retryable;
+                    pub const RETRY_AFTER: Option<#retry_after_type> = #retry_after;
 
                     pub trait [< From #category:camel #error:camel >] {
 
-                        fn from() -> Self where Self: Sized;
+                        fn from(#from_args) -> Self where Self: Sized;
 
                     }
 
@@ -84,10 +226,35 @@ pub fn define_error0(crate_prefix: &TokenStream, attr: TokenStream) -> TokenStre
         });
     }
 
-    TokenStream::from_iter(result)
+    Ok(TokenStream::from_iter(result))
 }
 
-fn parse_error_content(stream: ParseStream) -> Result<(Ident, LitStr, Ident), syn::Error> {
+fn option(value: Option<TokenStream>) -> TokenStream {
+    value.map_or_else(|| quote! { None }, |value| quote! { Some(#value) })
+}
+
+fn parse_error_content(stream: ParseStream) -> Result<(Ident, ErrorDefinitionInner), syn::Error> {
+    let mut doc = Vec::new();
+    for attr in stream.call(Attribute::parse_outer)? {
+        match attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }) = meta.value
+                else {
+                    return Err(Error::new_spanned(meta.value, "Expected string"));
+                };
+                doc.push(line);
+            }
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "Only doc comments are supported on errors",
+                ));
+            }
+        }
+    }
     let name: Ident = stream.parse()?;
     let group: Group = stream.parse()?;
     if group.delimiter() != Delimiter::Parenthesis {
@@ -96,10 +263,7 @@ fn parse_error_content(stream: ParseStream) -> Result<(Ident, LitStr, Ident), sy
             "Expected parenthesis (error content)",
         ));
     }
-    let ErrorDefinitionInner {
-        description,
-        statuscode,
-    } = syn::parse2(group.stream())?;
-
-    Ok((name, description, statuscode))
+    let mut inner: ErrorDefinitionInner = syn::parse2(group.stream())?;
+    inner.doc = doc;
+    Ok((name, inner))
 }
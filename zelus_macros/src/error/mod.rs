@@ -4,48 +4,14 @@ pub mod define;
 use convert_case::{Case, Casing as _};
 use either::Either;
 use proc_macro2::{Delimiter, Group, Ident, Punct, Span, TokenStream, TokenTree};
-use quote::quote;
+use quote::{ToTokens as _, quote, quote_spanned};
 use std::collections::{HashMap, HashSet};
 use syn::parse::{Parse, ParseStream};
 use syn::token::{Brace, Bracket};
 use syn::{LitStr, Token};
 
-// Taken from https://stackoverflow.com/a/73727708
-// Allows multiple errors with the same status code
-const INVISIBLE_CHARS_HEX: &[u32] = &[
-    0x00AD, 0x061C, 0x180E, 0x200B, 0x200C, 0x200D, 0x200E, 0x200F, 0x202A, 0x202B, 0x202C, 0x202D,
-    0x202E, 0x2060, 0x2061, 0x2062, 0x2063, 0x2064, 0x2067, 0x2066, 0x2068, 0x2069, 0x206A, 0x206B,
-    0x206C, 0x206D, 0x206E, 0x206F, 0xFEFF, 0x1D173, 0x1D174, 0x1D175, 0x1D176, 0x1D177, 0x1D178,
-    0x1D179, 0x1D17A, 0xE0001, 0xE0020, 0xE0021, 0xE0022, 0xE0023, 0xE0024, 0xE0025, 0xE0026,
-    0xE0027, 0xE0028, 0xE0029, 0xE002A, 0xE002B, 0xE002C, 0xE002D, 0xE002E, 0xE002F, 0xE0030,
-    0xE0031, 0xE0032, 0xE0033, 0xE0034, 0xE0035, 0xE0036, 0xE0037, 0xE0038, 0xE0039, 0xE003A,
-    0xE003B, 0xE003C, 0xE003D, 0xE003E, 0xE003F, 0xE0040, 0xE0041, 0xE0042, 0xE0043, 0xE0044,
-    0xE0045, 0xE0046, 0xE0047, 0xE0048, 0xE0049, 0xE004A, 0xE004B, 0xE004C, 0xE004D, 0xE004E,
-    0xE004F, 0xE0050, 0xE0051, 0xE0052, 0xE0053, 0xE0054, 0xE0055, 0xE0056, 0xE0057, 0xE0058,
-    0xE0059, 0xE005A, 0xE005B, 0xE005C, 0xE005D, 0xE005E, 0xE005F, 0xE0060, 0xE0061, 0xE0062,
-    0xE0063, 0xE0064, 0xE0065, 0xE0066, 0xE0067, 0xE0068, 0xE0069, 0xE006A, 0xE006B, 0xE006C,
-    0xE006D, 0xE006E, 0xE006F, 0xE0070, 0xE0071, 0xE0072, 0xE0073, 0xE0074, 0xE0075, 0xE0076,
-    0xE0077, 0xE0078, 0xE0079, 0xE007A, 0xE007B, 0xE007C, 0xE007D, 0xE007E, 0xE007F,
-];
-
-#[expect(clippy::indexing_slicing, reason = "this is a const evaluation")]
-const INVISIBLE_CHARS: [char; INVISIBLE_CHARS_HEX.len()] = {
-    let mut chars = [' '; INVISIBLE_CHARS_HEX.len()];
-
-    let mut pos = 0;
-    while pos < INVISIBLE_CHARS_HEX.len() {
-        if let Some(char) = char::from_u32(INVISIBLE_CHARS_HEX[pos]) {
-            chars[pos] = char;
-        } else {
-            panic!("Invisible Char is not actually a char");
-        }
-        pos += 1;
-    }
-    chars
-};
-
-type ErrorAttributes = Vec<(Ident, Span, Option<Ident>)>;
-type ErrorInner = Either<(Ident, Ident, LitStr), (Ident, Ident)>;
+type ErrorAttributes = Vec<(Ident, Span, Option<ErrorAttributeEntry>)>;
+type ErrorInner = Either<(Ident, Ident, LitStr), (Ident, Ident, bool)>;
 
 pub struct ErrorContent {
     pub errors: Vec<(ErrorInner, ErrorAttributes)>,
@@ -68,8 +34,11 @@ impl Parse for ErrorContent {
                     return Err(syn::Error::new(content.span(), "Expected parenthesis"));
                 }
                 let content: ErrorCategoryContent = syn::parse2(content.stream())?;
-                for (error, attributes) in content.vec {
-                    errors.push((Either::Right((category.clone(), error)), attributes));
+                for (error, details, attributes) in content.vec {
+                    errors.push((
+                        Either::Right((category.clone(), error, details)),
+                        attributes,
+                    ));
                 }
             } else if input.peek(Brace) {
                 let custom: Group = input.parse()?;
@@ -95,7 +64,7 @@ impl Parse for ErrorContent {
 }
 
 pub struct ErrorCategoryContent {
-    pub vec: Vec<(Ident, ErrorAttributes)>,
+    pub vec: Vec<(Ident, bool, ErrorAttributes)>,
 }
 
 impl Parse for ErrorCategoryContent {
@@ -104,6 +73,14 @@ impl Parse for ErrorCategoryContent {
 
         while !input.is_empty() {
             let error: Ident = input.parse()?;
+            // `{ .. }` marks errors defined with details
+            let details = if input.peek(Brace) {
+                let group: Group = input.parse()?;
+                let _rest: Token![..] = syn::parse2(group.stream())?;
+                true
+            } else {
+                false
+            };
             let attributes = if input.peek(Bracket) {
                 let group: Group = input.parse()?;
                 let attributes: ErrorAttributesContent = syn::parse2(group.stream())?;
@@ -111,7 +88,7 @@ impl Parse for ErrorCategoryContent {
             } else {
                 Vec::new()
             };
-            vec.push((error, attributes));
+            vec.push((error, details, attributes));
         }
 
         Ok(Self { vec })
@@ -151,8 +128,58 @@ impl Parse for ErrorCustomContent {
     }
 }
 
+/// Specific entry of an error attribute, like `NotFound`, `404` or `unique(users_email_key)`.
+#[derive(Clone)]
+pub struct ErrorAttributeEntry {
+    pub key: TokenTree,
+    pub args: Option<Group>,
+}
+
+impl ErrorAttributeEntry {
+    fn span(&self) -> Span {
+        self.key.span()
+    }
+
+    fn without_args(&self, attr: &str) -> syn::Result<&TokenTree> {
+        if let Some(args) = &self.args {
+            return Err(syn::Error::new(
+                args.span(),
+                format!(
+                    "The entry {} for {attr} in error takes no arguments",
+                    self.key
+                ),
+            ));
+        }
+        Ok(&self.key)
+    }
+
+    /// The name or string passed as argument, like `users_email_key` or `"40001"`.
+    #[cfg(any(feature = "sqlx", feature = "redis"))]
+    fn arg(&self) -> syn::Result<Option<LitStr>> {
+        self.args
+            .as_ref()
+            .map(|args| {
+                let arg: TokenTree = syn::parse2(args.stream())?;
+                match arg {
+                    TokenTree::Ident(ident) => Ok(LitStr::new(&ident.to_string(), ident.span())),
+                    arg => syn::parse2(arg.into()),
+                }
+            })
+            .transpose()
+    }
+}
+
+impl core::fmt::Display for ErrorAttributeEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.args {
+            Some(args) => write!(f, "{}({})", self.key, args.stream()),
+            None => write!(f, "{}", self.key),
+        }
+    }
+}
+
 pub struct ErrorAttributesContent {
-    pub vec: Vec<(Ident, Span, Option<Ident>)>,
+    pub vec: ErrorAttributes,
 }
 
 impl Parse for ErrorAttributesContent {
@@ -169,8 +196,24 @@ impl Parse for ErrorAttributesContent {
                 let value: Punct = input.parse()?;
                 vec.push((key, value.span(), None));
             } else {
-                let value: Ident = input.parse()?;
-                vec.push((key, value.span(), Some(value)));
+                // Identifiers like `NotFound`, or status codes like `404`
+                let value: TokenTree = input.parse()?;
+                if !matches!(value, TokenTree::Ident(_) | TokenTree::Literal(_)) {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "Expected identifier, literal or *",
+                    ));
+                }
+                let args = if input.peek(syn::token::Paren) {
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                vec.push((
+                    key,
+                    value.span(),
+                    Some(ErrorAttributeEntry { key: value, args }),
+                ));
             }
         }
 
@@ -178,15 +221,42 @@ impl Parse for ErrorAttributesContent {
     }
 }
 
+/// The name `[< #name:camel >]` generates, like `camel` in `pastey` 0.1.1 (`src/segment.rs`), so
+/// the variants of the errors of a category keep the names they had when pasted.
+fn camel(name: &str) -> String {
+    let mut camel = String::new();
+    let mut prev = '_';
+    for ch in name.chars() {
+        if ch != '_' {
+            if prev == '_' {
+                camel.extend(ch.to_uppercase());
+            } else if prev.is_uppercase() {
+                camel.extend(ch.to_lowercase());
+            } else {
+                camel.push(ch);
+            }
+        }
+        prev = ch;
+    }
+    camel
+}
+
 pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
     let ErrorContent { ident, mut errors } = syn::parse2(input)?;
 
     let mut content = TokenStream::new();
     let mut values_impl = TokenStream::new();
-    let mut response = TokenStream::new();
+    let mut info = TokenStream::new();
+    let mut display = TokenStream::new();
     let mut from = TokenStream::new();
+    let mut payload = TokenStream::new();
     let mut openapi = TokenStream::new();
     let mut impls = TokenStream::new();
+    let mut parts = TokenStream::new();
+    let mut from_parts = TokenStream::new();
+    let mut retryable = TokenStream::new();
+    let mut causes = TokenStream::new();
+    let none = quote! { #crate_prefix error::ErrorCause::none() };
 
     errors.insert(
         0,
@@ -200,20 +270,52 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
         ),
     );
 
+    // The variants are generated with these identifiers, so collisions are checked on the actual
+    // names
+    let mut variants = HashMap::new();
+    let mut variant_idents = Vec::with_capacity(errors.len());
+    for (index, (error, _attributes)) in errors.iter().enumerate() {
+        let (id, variant, span) = match error {
+            Either::Left((error_ident, _statuscode, _msg)) => (
+                error_ident.to_string().to_case(Case::Snake),
+                error_ident.to_string(),
+                error_ident.span(),
+            ),
+            Either::Right((category, error_ident, _details)) => (
+                format!("{category}/{error_ident}"),
+                format!(
+                    "{}{}",
+                    camel(&category.to_string()),
+                    camel(&error_ident.to_string())
+                ),
+                error_ident.span(),
+            ),
+        };
+        // The first entry is `Communication`
+        if index > 0 && ["Communication", "Unknown", "Foreign"].contains(&variant.as_str()) {
+            return Err(syn::Error::new(
+                span,
+                format!("The variant {variant} is reserved for the generated error"),
+            ));
+        }
+        match variants.insert(variant.clone(), id.clone()) {
+            Some(other) if other == id => {
+                return Err(syn::Error::new(span, format!("Duplicate error {id}")));
+            }
+            Some(other) => {
+                return Err(syn::Error::new(
+                    span,
+                    format!("The errors {other} and {id} both generate the variant {variant}"),
+                ));
+            }
+            None => {}
+        }
+        variant_idents.push(Ident::new(&variant, span));
+    }
+
     let mut attributes_parsed = HashMap::new();
     let mut errors2 = Vec::with_capacity(errors.len());
-    for (index, (error, attributes)) in errors.into_iter().enumerate() {
-        let error_suffix = INVISIBLE_CHARS
-            .get(index)
-            .ok_or_else(|| {
-                syn::Error::new(
-                    ident.span(),
-                    "Too many errors. Not enough invisible chars hardcoded",
-                )
-            })?
-            .to_string(); // So I can have multiple errors in openapi with the same status code
-
-        let error_suffix = LitStr::new(&error_suffix, Span::call_site());
+    for ((error, attributes), variant) in errors.into_iter().zip(variant_idents) {
         let entry = match error {
             Either::Left((error_ident, statuscode, msg)) => {
                 let error_id = LitStr::new(
@@ -223,44 +325,81 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
 
                 if error_ident.to_string().eq("Communication") {
                     content.extend(quote! {
-                        #error_ident (#crate_prefix reqwest::Error),
+                        /// Failed request of the SDK. `reqwest` errors are kept as
+                        /// `ZelusClientError::Reqwest`, match on `Communication(ZelusClientError::Reqwest(err), _)`
+                        /// where `Communication(err)` was used with a `reqwest::Error`.
+                        ///
+                        /// Answered with `502 Bad Gateway` when returned by a route.
+                        #error_ident (#crate_prefix sdk::ZelusClientError, #crate_prefix error::ErrorCause),
+                    });
+
+                    info.extend(quote! {
+                        #error_ident(err, _cause) => #crate_prefix internal::upstream_error(None, None, None, err.is_retryable()),
                     });
 
-                    response.extend(quote! {
-                        #error_ident(_err) => panic!("You tried to use the communication error entry as response"),
+                    causes.extend(quote! {
+                        #error_ident(_err, cause) => cause,
                     });
                 } else {
                     content.extend(quote! {
-                        #error_ident,
+                        #error_ident(#crate_prefix error::ErrorCause),
                     });
 
                     from.extend(quote! {
-                        #error_id => Ok(#error_ident),
+                        #error_id => Ok(#error_ident(#none)),
                     });
 
-                    response.extend(quote! {
-                        #error_ident => (#crate_prefix http::StatusCode::#statuscode,#crate_prefix axum::extract::Json(serde_json::json!({
-                            "error": {
-                                "id": #error_id,
-                                "msg": #msg,
-                            }
-                        }))).into_response(),
+                    display.extend(quote! {
+                        #error_ident(_cause) => #msg,
                     });
 
-                    openapi.extend(quote! {
-                        responses = responses.response(
-                            format!("{}{}", #crate_prefix http::StatusCode::#statuscode.as_str(), #error_suffix),
-                            #crate_prefix utoipa::openapi::ResponseBuilder::new()
-                                .description(#msg)
-                                .content(
-                                    "application/json",
-                                    #crate_prefix utoipa::openapi::content::Content::new(Some(
-                                        #crate_prefix utoipa::openapi::RefOr::T(#crate_prefix internal::error_schema(#error_id, #msg))
-                                    )),
-                                )
-                                .build(),
-                        );
+                    payload.extend(quote! {
+                        #error_id => Some(Ok(#error_ident(#none))),
+                    });
+
+                    causes.extend(quote! {
+                        #error_ident(cause) => cause,
+                    });
 
+                    parts.extend(quote! {
+                        #error_ident(_cause) => #crate_prefix error::ErrorParts::Variant {
+                            id: #error_id,
+                            msg: #msg,
+                            code: #crate_prefix http::StatusCode::#statuscode,
+                            details: None,
+                        },
+                    });
+
+                    // Errors defined inline only share the id, unlike the errors of a category
+                    from_parts.extend(quote! {
+                        #crate_prefix error::ErrorParts::Variant { id: #error_id, msg, code, details: None }
+                            if msg == #msg && code == #crate_prefix http::StatusCode::#statuscode => #error_ident(#none),
+                    });
+
+                    info.extend(quote! {
+                        #error_ident(_cause) => #crate_prefix error::ErrorInfo {
+                            id: #error_id,
+                            msg: #msg,
+                            code: #crate_prefix http::StatusCode::#statuscode,
+                            details: None,
+                            error_code: None,
+                            help_url: None,
+                            retryable: false,
+                            retry_after: None,
+                        },
+                    });
+
+                    retryable.extend(quote! {
+                        #error_ident(_cause) => false,
+                    });
+
+                    openapi.extend(quote! {
+                        errors.push((
+                            #crate_prefix http::StatusCode::#statuscode,
+                            #msg,
+                            #crate_prefix internal::error_schema(#error_id, #msg, None, None, None, None),
+                            false,
+                        ));
                     });
 
                     values_impl.extend(quote! {
@@ -268,45 +407,83 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
                             id: #error_id,
                             msg: #msg,
                             code: #crate_prefix http::StatusCode::#statuscode,
-                            instance: &Self::#error_ident,
+                            instance: &#ident::#error_ident(#none),
+                            error_code: None,
+                            help_url: None,
+                            documentation: None,
+                            retryable: false,
                         },
                     });
                 }
                 quote! { [< #error_ident >] }
             }
-            Either::Right((category, error_ident)) => {
+            Either::Right((category, error_ident, false)) => {
                 let error_id = LitStr::new(&format!("{category}/{error_ident}"), Span::call_site());
 
                 from.extend(quote! {
-                    #error_id => Ok([< #category:camel #error_ident:camel >]),
+                    #error_id => Ok(#variant(#none)),
+                });
+
+                payload.extend(quote! {
+                    #error_id => Some(Ok(#variant(#none))),
+                });
+
+                causes.extend(quote! {
+                    #variant(cause) => cause,
+                });
+
+                parts.extend(quote! {
+                    #variant(_cause) => #crate_prefix error::ErrorParts::Variant {
+                        id: #error_id,
+                        msg: [< error_ #category _ #error_ident >]::DESCRIPTION,
+                        code: [< error_ #category _ #error_ident >]::STATUSCODE,
+                        details: None,
+                    },
+                });
+
+                from_parts.extend(quote! {
+                    #crate_prefix error::ErrorParts::Variant { id: #error_id, details: None, .. } => #variant(#none),
+                });
+
+                retryable.extend(quote! {
+                    #variant(_cause) => [< error_ #category _ #error_ident >]::RETRYABLE,
                 });
 
                 content.extend(quote! {
-                    [< #category:camel #error_ident:camel >],
+                    #variant(#crate_prefix error::ErrorCause),
                 });
 
-                response.extend(quote! {
-                    [< #category:camel #error_ident:camel >] => ( [< error_ #category _ #error_ident >]::STATUSCODE, #crate_prefix axum::extract::Json(serde_json::json!({
-                        "error": {
-                            "id": #error_id,
-                            "msg": [< error_ #category _ #error_ident >]::DESCRIPTION,
-                        }
-                    }))).into_response(),
+                info.extend(quote! {
+                    #variant(_cause) => #crate_prefix error::ErrorInfo {
+                        id: #error_id,
+                        msg: [< error_ #category _ #error_ident >]::DESCRIPTION,
+                        code: [< error_ #category _ #error_ident >]::STATUSCODE,
+                        details: None,
+                        error_code: [< error_ #category _ #error_ident >]::CODE,
+                        help_url: [< error_ #category _ #error_ident >]::HELP_URL,
+                        retryable: [< error_ #category _ #error_ident >]::RETRYABLE,
+                        retry_after: [< error_ #category _ #error_ident >]::RETRY_AFTER.map(|retry_after| retry_after()),
+                    },
+                });
+
+                display.extend(quote! {
+                    #variant(_cause) => [< error_ #category _ #error_ident >]::DESCRIPTION,
                 });
 
                 openapi.extend(quote! {
-                    responses = responses.response(
-                        format!("{}{}", [< error_ #category _ #error_ident >]::STATUSCODE.as_str(), #error_suffix),
-                        #crate_prefix utoipa::openapi::ResponseBuilder::new()
-                            .description([< error_ #category _ #error_ident >]::DESCRIPTION)
-                            .content(
-                                "application/json",
-                                #crate_prefix utoipa::openapi::content::Content::new(Some(
-                                    #crate_prefix utoipa::openapi::RefOr::T(#crate_prefix internal::error_schema(#error_id, [< error_ #category _ #error_ident >]::DESCRIPTION))
-                                )),
-                            )
-                            .build(),
-                    );
+                    errors.push((
+                        [< error_ #category _ #error_ident >]::STATUSCODE,
+                        [< error_ #category _ #error_ident >]::DESCRIPTION,
+                        #crate_prefix internal::error_schema(
+                            #error_id,
+                            [< error_ #category _ #error_ident >]::DESCRIPTION,
+                            None,
+                            [< error_ #category _ #error_ident >]::CODE,
+                            [< error_ #category _ #error_ident >]::HELP_URL,
+                            [< error_ #category _ #error_ident >]::DOCUMENTATION,
+                        ),
+                        [< error_ #category _ #error_ident >]::RETRY_AFTER.is_some(),
+                    ));
                 });
 
                 impls.extend(quote! {
@@ -314,7 +491,7 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
                     impl [< error_ #category _ #error_ident >]::[< From #category:camel #error_ident:camel >] for #ident {
 
                         fn from() -> Self {
-                            Self::[< #category:camel #error_ident:camel >]
+                            Self::#variant(#none)
                         }
 
                     }
@@ -324,10 +501,109 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
                         id: #error_id,
                         msg: [< error_ #category _ #error_ident >]::DESCRIPTION,
                         code: [< error_ #category _ #error_ident >]::STATUSCODE,
-                        instance: &Self::[< #category:camel #error_ident:camel >],
+                        instance: &#ident::#variant(#none),
+                        error_code: [< error_ #category _ #error_ident >]::CODE,
+                        help_url: [< error_ #category _ #error_ident >]::HELP_URL,
+                        documentation: [< error_ #category _ #error_ident >]::DOCUMENTATION,
+                        retryable: [< error_ #category _ #error_ident >]::RETRYABLE,
                     },
                 });
-                quote! { [< #category:camel #error_ident:camel >] }
+                quote! { #variant }
+            }
+            Either::Right((category, error_ident, true)) => {
+                let error_id = LitStr::new(&format!("{category}/{error_ident}"), Span::call_site());
+
+                if let Some((attr, _key_span, _key)) = attributes.first() {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        "Errors with details cannot be used by error attributes",
+                    ));
+                }
+
+                payload.extend(quote! {
+                    #error_id => Some(#crate_prefix serde_json::from_value(details).map(|details| #variant(details, #none))),
+                });
+
+                causes.extend(quote! {
+                    #variant(_details, cause) => cause,
+                });
+
+                parts.extend(quote! {
+                    #variant(details, _cause) => #crate_prefix error::ErrorParts::Variant {
+                        id: #error_id,
+                        msg: [< error_ #category _ #error_ident >]::DESCRIPTION,
+                        code: [< error_ #category _ #error_ident >]::STATUSCODE,
+                        details: Some(Box::new(details)),
+                    },
+                });
+
+                from_parts.extend(quote! {
+                    #crate_prefix error::ErrorParts::Variant { id: #error_id, msg, code, details: Some(details) } => match details.downcast() {
+                        Ok(details) => #variant(*details, #none),
+                        Err(details) => return Err((#crate_prefix error::ErrorParts::Variant { id: #error_id, msg, code, details: Some(details) }, source)),
+                    },
+                });
+
+                retryable.extend(quote! {
+                    #variant(_details, _cause) => [< error_ #category _ #error_ident >]::RETRYABLE,
+                });
+
+                content.extend(quote! {
+                    #variant([< error_ #category _ #error_ident >]::[< #category:camel #error_ident:camel Details >], #crate_prefix error::ErrorCause),
+                });
+
+                info.extend(quote! {
+                    #variant(details, _cause) => #crate_prefix error::ErrorInfo {
+                        id: #error_id,
+                        msg: [< error_ #category _ #error_ident >]::DESCRIPTION,
+                        code: [< error_ #category _ #error_ident >]::STATUSCODE,
+                        details: #crate_prefix internal::error_details(#error_id, details),
+                        error_code: [< error_ #category _ #error_ident >]::CODE,
+                        help_url: [< error_ #category _ #error_ident >]::HELP_URL,
+                        retryable: [< error_ #category _ #error_ident >]::RETRYABLE,
+                        retry_after: [< error_ #category _ #error_ident >]::RETRY_AFTER.map(|retry_after| retry_after(details)),
+                    },
+                });
+
+                display.extend(quote! {
+                    #variant(_details, _cause) => [< error_ #category _ #error_ident >]::DESCRIPTION,
+                });
+
+                openapi.extend(quote! {
+                    schemas.insert(
+                        < [< error_ #category _ #error_ident >]::[< #category:camel #error_ident:camel Details >] as #crate_prefix utoipa::ToSchema >::name().to_string(),
+                        < [< error_ #category _ #error_ident >]::[< #category:camel #error_ident:camel Details >] as #crate_prefix utoipa::PartialSchema >::schema(),
+                    );
+                    errors.push((
+                        [< error_ #category _ #error_ident >]::STATUSCODE,
+                        [< error_ #category _ #error_ident >]::DESCRIPTION,
+                        #crate_prefix internal::error_schema(
+                            #error_id,
+                            [< error_ #category _ #error_ident >]::DESCRIPTION,
+                            Some(#crate_prefix utoipa::openapi::RefOr::Ref(
+                                #crate_prefix utoipa::openapi::schema::RefBuilder::new()
+                                    .ref_location_from_schema_name(< [< error_ #category _ #error_ident >]::[< #category:camel #error_ident:camel Details >] as #crate_prefix utoipa::ToSchema >::name())
+                                    .build()
+                            )),
+                            [< error_ #category _ #error_ident >]::CODE,
+                            [< error_ #category _ #error_ident >]::HELP_URL,
+                            [< error_ #category _ #error_ident >]::DOCUMENTATION,
+                        ),
+                        [< error_ #category _ #error_ident >]::RETRY_AFTER.is_some(),
+                    ));
+                });
+
+                impls.extend(quote! {
+                    #[automatically_derived]
+                    impl [< error_ #category _ #error_ident >]::[< From #category:camel #error_ident:camel >] for #ident {
+
+                        fn from(details: [< error_ #category _ #error_ident >]::[< #category:camel #error_ident:camel Details >]) -> Self {
+                            Self::#variant(details, #none)
+                        }
+
+                    }
+                });
+                quote! { #variant }
             }
         };
 
@@ -376,120 +652,453 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
         }
     }
 
+    // Without `upstream`, `reqwest::Error` is converted into `Communication`
+    let upstream = attributes_parsed.contains_key("upstream");
+
     for (attr, (attr_span, def, other)) in attributes_parsed {
+        let attr_ident = Ident::new(&attr, attr_span);
         match attr.as_str() {
             #[cfg(feature = "io")]
             "io" => {
                 let mut matches = TokenStream::new();
                 for (kind, variant) in other {
-                    matches.extend(quote! { ErrorKind::#kind => Self::#variant, });
+                    let kind = kind.without_args(&attr)?;
+                    matches.extend(quote! { ErrorKind::#kind => Self::#variant(#none), });
                 }
-                impls.extend(quote! {
-                    impl From<std::io::Error> for #ident {
-
-                        fn from(err: std::io::Error) -> Self {
-                            use std::io::ErrorKind;
-                            match err.kind() {
-                                #matches
-                                _ => Self::#def,
-                            }
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { std::io::Error },
+                    &quote! {
+                        use std::io::ErrorKind;
+                        match err.kind() {
+                            #matches
+                            _ => Self::#def(#none),
                         }
-
-                    }
-                });
+                    },
+                ));
             }
             #[cfg(feature = "sqlx")]
             "sql" => {
                 let mut matches = TokenStream::new();
-                let mut database_matches = TokenStream::new();
+                // Checked in the order of declaration
                 for (kind, variant) in other {
-                    match kind.to_string().as_str() {
-                        "not_found" => {
+                    let condition = match (kind.key.to_string().as_str(), kind.arg()?) {
+                        ("not_found", None) => {
                             matches.extend(quote! {
                                 if let #crate_prefix sqlx::Error::RowNotFound = err {
-                                    return Self::#variant;
+                                    return Self::#variant(#none);
                                 }
                             });
+                            continue;
                         }
-                        "check" => {
-                            database_matches.extend(quote! {
-                                    if database.is_check_violation() {
-                                        return Self::#variant;
-                                    }
-                            });
-                        }
-                        "foreign_key" => {
-                            database_matches.extend(quote! {
-                                    if database.is_foreign_key_violation() {
-                                        return Self::#variant;
-                                    }
-                            });
+                        ("check", None) => quote! { database.is_check_violation() },
+                        ("foreign_key", None) => quote! { database.is_foreign_key_violation() },
+                        ("unique", None) => quote! { database.is_unique_violation() },
+                        ("check", Some(name)) => quote! {
+                            database.is_check_violation() && #crate_prefix internal::sql_constraint(&**database) == Some(#name)
+                        },
+                        ("foreign_key", Some(name)) => quote! {
+                            database.is_foreign_key_violation() && #crate_prefix internal::sql_constraint(&**database) == Some(#name)
+                        },
+                        ("unique", Some(name)) => quote! {
+                            database.is_unique_violation() && #crate_prefix internal::sql_constraint(&**database) == Some(#name)
+                        },
+                        ("constraint", Some(name)) => quote! {
+                            #crate_prefix internal::sql_constraint(&**database) == Some(#name)
+                        },
+                        ("code", Some(code)) => quote! {
+                            database.code().as_deref() == Some(#code)
+                        },
+                        _ => {
+                            return Err(syn::Error::new(
+                                kind.span(),
+                                format!("Unknown entry {kind} for {attr} in error"),
+                            ));
                         }
-                        "unique" => {
-                            database_matches.extend(quote! {
-                                    if database.is_unique_violation() {
-                                        return Self::#variant;
-                                    }
-                            });
+                    };
+                    matches.extend(quote! {
+                        if matches!(err, #crate_prefix sqlx::Error::Database(database) if #condition) {
+                            return Self::#variant(#none);
                         }
+                    });
+                }
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #crate_prefix sqlx::Error },
+                    &quote! {
+                        #matches
+                        Self::#def(#none)
+                    },
+                ));
+            }
+            #[cfg(feature = "redis")]
+            "redis" => {
+                let mut matches = TokenStream::new();
+                // Checked in the order of declaration
+                for (kind, variant) in other {
+                    let condition = match (kind.key.to_string().as_str(), kind.arg()?) {
+                        ("io", None) => quote! { err.is_io_error() },
+                        ("connection", None) => quote! {
+                            err.is_connection_refusal() || err.is_connection_dropped()
+                        },
+                        ("timeout", None) => quote! { err.is_timeout() },
+                        ("type", None) => quote! {
+                            err.kind() == #crate_prefix redis::ErrorKind::UnexpectedReturnType
+                        },
+                        ("parse", None) => quote! {
+                            err.kind() == #crate_prefix redis::ErrorKind::Parse
+                        },
+                        ("auth", None) => quote! {
+                            matches!(
+                                err.kind(),
+                                #crate_prefix redis::ErrorKind::AuthenticationFailed
+                                    | #crate_prefix redis::ErrorKind::Server(#crate_prefix redis::ServerErrorKind::NoPerm)
+                            )
+                        },
+                        ("busy", None) => quote! {
+                            matches!(
+                                err.kind(),
+                                #crate_prefix redis::ErrorKind::Server(
+                                    #crate_prefix redis::ServerErrorKind::BusyLoading
+                                        | #crate_prefix redis::ServerErrorKind::TryAgain
+                                )
+                            ) || err.code() == Some("BUSY")
+                        },
+                        ("cluster", None) => quote! { err.is_cluster_error() },
+                        ("code", Some(code)) => quote! { err.code() == Some(#code) },
                         _ => {
                             return Err(syn::Error::new(
                                 kind.span(),
                                 format!("Unknown entry {kind} for {attr} in error"),
                             ));
                         }
-                    }
-                }
-                if !database_matches.is_empty() {
+                    };
                     matches.extend(quote! {
-                        if let #crate_prefix sqlx::Error::Database(ref database) = err {
-                            #database_matches
+                        if #condition {
+                            return Self::#variant(#none);
                         }
                     });
                 }
-                impls.extend(quote! {
-                    #[automatically_derived]
-                    impl From<#crate_prefix sqlx::Error> for #ident {
-
-                        fn from(err: #crate_prefix sqlx::Error) -> Self {
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #crate_prefix redis::RedisError },
+                    &quote! {
+                        #matches
+                        Self::#def(#none)
+                    },
+                ));
+            }
+            #[cfg(not(feature = "io"))]
+            "io" => {
+                return Err(syn::Error::new(
+                    attr_span,
+                    "The io error attribute requires the io feature of zelus",
+                ));
+            }
+            #[cfg(not(feature = "sqlx"))]
+            "sql" => {
+                return Err(syn::Error::new(
+                    attr_span,
+                    "The sql error attribute requires the sqlx feature of zelus",
+                ));
+            }
+            #[cfg(not(feature = "redis"))]
+            "redis" => {
+                return Err(syn::Error::new(
+                    attr_span,
+                    "The redis error attribute requires the redis feature of zelus",
+                ));
+            }
+            "json" => {
+                let mut matches = TokenStream::new();
+                for (kind, variant) in other {
+                    let category = match kind.without_args(&attr)?.to_string().as_str() {
+                        "io" => quote! { Io },
+                        "syntax" => quote! { Syntax },
+                        "data" => quote! { Data },
+                        "eof" => quote! { Eof },
+                        _ => {
+                            return Err(syn::Error::new(
+                                kind.span(),
+                                format!("Unknown entry {kind} for {attr} in error"),
+                            ));
+                        }
+                    };
+                    matches.extend(quote! { Category::#category => Self::#variant(#none), });
+                }
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #crate_prefix serde_json::Error },
+                    &quote! {
+                        use #crate_prefix serde_json::error::Category;
+                        #[allow(unreachable_patterns)]
+                        match err.classify() {
                             #matches
-                            Self::#def
+                            _ => Self::#def(#none),
                         }
-
-                    }
-                });
+                    },
+                ));
             }
-            #[cfg(feature = "redis")]
-            "redis" => {
+            "timeout" => {
                 if let Some((kind, _variant)) = other.first() {
                     return Err(syn::Error::new(
                         kind.span(),
                         format!("Unknown entry {kind} for {attr} in error"),
                     ));
                 }
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #crate_prefix tokio::time::error::Elapsed },
+                    &quote! {
+                        Self::#def(#none)
+                    },
+                ));
+            }
+            "upstream" => {
+                let mut matches = TokenStream::new();
+                for (kind, variant) in other {
+                    let status =
+                        syn::parse2::<syn::LitInt>(kind.without_args(&attr)?.clone().into())
+                            .and_then(|status| status.base10_parse::<u16>())
+                            .ok()
+                            .filter(|status| (100..=999).contains(status))
+                            .ok_or_else(|| {
+                                syn::Error::new(
+                                    kind.span(),
+                                    format!(
+                                        "Expected status code for {attr} in error, found {kind}"
+                                    ),
+                                )
+                            })?;
+                    matches.extend(quote! { Some(#status) => Self::#variant(#none), });
+                }
                 impls.extend(quote! {
                     #[automatically_derived]
-                    impl From<#crate_prefix redis::RedisError> for #ident {
+                    impl #ident {
 
-                        fn from(_err: #crate_prefix redis::RedisError) -> Self {
-                            Self::#def
+                        /// The variant of the `upstream` attribute for the status of a failed
+                        /// request to another service.
+                        fn upstream(status: Option<#crate_prefix http::StatusCode>) -> Self {
+                            match status.map(|status| status.as_u16()) {
+                                #matches
+                                _ => Self::#def(#none),
+                            }
                         }
 
                     }
                 });
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #crate_prefix reqwest::Error },
+                    &quote! { Self::upstream(err.status()) },
+                ));
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #crate_prefix sdk::ZelusClientError },
+                    &quote! { Self::upstream(err.status()) },
+                ));
             }
+            // Registered with `define_error_source!`, an unregistered attribute fails to resolve the
+            // module at the attribute
             _ => {
-                return Err(syn::Error::new(attr_span, "Unknown error attribute"));
+                let source =
+                    quote_spanned! { attr_span => [< error_source_ #attr_ident:snake >]::Source };
+                let mut matches = TokenStream::new();
+                let mut checks = TokenStream::new();
+                for (kind, variant) in other {
+                    let key = match kind.without_args(&attr)? {
+                        TokenTree::Literal(literal) => {
+                            syn::parse2::<LitStr>(literal.to_token_stream())
+                                .map_or_else(|_err| literal.to_string(), |literal| literal.value())
+                        }
+                        key => key.to_string(),
+                    };
+                    let message = format!("Unknown entry {key} for {attr} in error");
+                    checks.extend(quote_spanned! { kind.span() =>
+                        const _: () = assert!(#crate_prefix internal::source_key::<#source>(#key), #message);
+                    });
+                    matches.extend(quote! { Some(#key) => Self::#variant(#none), });
+                }
+                impls.extend(source_impls(
+                    crate_prefix,
+                    &ident,
+                    &quote! { #source },
+                    &quote! {
+                        #checks
+                        match #crate_prefix error::ZelusErrorSource::classify(err) {
+                            #matches
+                            _ => Self::#def(#none),
+                        }
+                    },
+                ));
             }
         }
     }
 
+    // With `upstream`, failed requests to other services and their errors are converted by status
+    let (communication_from, communication_parts, upstream_parts) = if upstream {
+        (
+            TokenStream::new(),
+            quote! {
+                #crate_prefix error::ErrorParts::Communication(err) => {
+                    let error = Self::upstream(err.status());
+                    return Ok(error.with_source(source.unwrap_or_else(|| Box::new(err))));
+                }
+            },
+            quote! {
+                #crate_prefix error::ErrorParts::Variant { code, .. } => Self::upstream(Some(code)),
+                #crate_prefix error::ErrorParts::Unknown { status, .. } => Self::upstream(Some(status)),
+            },
+        )
+    } else {
+        (
+            quote! {
+                #[automatically_derived]
+                impl From<#crate_prefix reqwest::Error> for #ident {
+
+                    fn from(err: #crate_prefix reqwest::Error) -> Self {
+                        Self::Communication(err.into(), #none)
+                    }
+
+                }
+
+                #[automatically_derived]
+                impl From<#crate_prefix sdk::ZelusClientError> for #ident {
+
+                    fn from(err: #crate_prefix sdk::ZelusClientError) -> Self {
+                        Self::Communication(err, #none)
+                    }
+
+                }
+            },
+            quote! {
+                #crate_prefix error::ErrorParts::Communication(err) => Communication(err, #none),
+            },
+            TokenStream::new(),
+        )
+    };
+
     Ok(quote! {
         #crate_prefix paste! {
             #[derive(Debug)]
             #[automatically_derived]
             pub enum #ident {
                 #content
+                /// Error response received by the SDK with an id unknown to this version, or without
+                /// an error payload, like an error page of a proxy.
+                ///
+                /// Answered with `502 Bad Gateway` and the status, id and message as details when
+                /// returned by a route, like `Communication`.
+                Unknown {
+                    status: #crate_prefix http::StatusCode,
+                    id: Option<String>,
+                    msg: Option<String>,
+                    body: String,
+                    /// The `Retry-After` header of the response, sent again when returned by a route.
+                    retry_after: Option<std::time::Duration>,
+                    cause: #crate_prefix error::ErrorCause,
+                },
+                /// Error converted from another generated error without a variant in this one,
+                /// rendered like the original error.
+                Foreign(#crate_prefix error::ErrorInfo, #crate_prefix error::ErrorCause),
+            }
+
+            #[automatically_derived]
+            impl #ident {
+
+                /// Keeps the cause of the error for logging, replacing a previous one.
+                #[must_use]
+                pub fn with_source(mut self, source: impl Into<#crate_prefix error::ErrorSource>) -> Self {
+                    *self.cause_mut() = #crate_prefix error::ErrorCause::new(source);
+                    self
+                }
+
+                /// The cause kept by `with_source` or `?`, never sent in the response.
+                #[must_use]
+                pub fn cause(&self) -> &#crate_prefix error::ErrorCause {
+                    use #ident::*;
+                    match self {
+                        #causes
+                        Unknown { cause, .. } | Foreign(_, cause) => cause,
+                    }
+                }
+
+                fn cause_mut(&mut self) -> &mut #crate_prefix error::ErrorCause {
+                    use #ident::*;
+                    match self {
+                        #causes
+                        Unknown { cause, .. } | Foreign(_, cause) => cause,
+                    }
+                }
+
+                /// Whether the request may succeed if repeated, for errors defined as `retryable`,
+                /// failed requests of the SDK and unknown responses of overloaded servers.
+                ///
+                /// Only requests without side effects, or whose effects don't add up, are safe to repeat.
+                #[must_use]
+                pub fn is_retryable(&self) -> bool {
+                    use #ident::*;
+                    match self {
+                        Communication(err, _cause) => err.is_retryable(),
+                        #retryable
+                        Unknown { status, .. } => #crate_prefix internal::retryable_status(*status),
+                        Foreign(info, _cause) => info.retryable,
+                    }
+                }
+
+                fn error_info(&self) -> #crate_prefix error::ErrorInfo {
+                    use #ident::*;
+                    match self {
+                        #info
+                        Foreign(info, _cause) => info.clone(),
+                        Unknown { status, id, msg, retry_after, .. } => #crate_prefix error::ErrorInfo {
+                            retry_after: *retry_after,
+                            ..#crate_prefix internal::upstream_error(
+                                Some(*status),
+                                id.as_deref(),
+                                msg.as_deref(),
+                                #crate_prefix internal::retryable_status(*status),
+                            )
+                        },
+                    }
+                }
+
+            }
+
+            #[automatically_derived]
+            impl std::fmt::Display for #ident {
+
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    use #ident::*;
+                    f.write_str(match self {
+                        Communication(_err, _cause) => "Invalid Request/Response",
+                        #display
+                        Foreign(info, _cause) => info.msg,
+                        Unknown { status, msg: Some(msg), .. } => return write!(f, "{msg} ({status})"),
+                        Unknown { status, .. } => return write!(f, "Unexpected error response ({status})"),
+                    })
+                }
+
+            }
+
+            #[automatically_derived]
+            impl std::error::Error for #ident {
+
+                fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                    match (self, self.cause().get()) {
+                        (_, Some(cause)) => Some(cause),
+                        (Self::Communication(err, _cause), None) => Some(err),
+                        (_, None) => None,
+                    }
+                }
+
             }
 
             #[automatically_derived]
@@ -510,11 +1119,7 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
             impl #crate_prefix axum::response::IntoResponse for #ident {
 
                 fn into_response(self) -> #crate_prefix http::response::Response<#crate_prefix axum::body::Body> {
-                    use #ident::*;
-                    #[allow(unreachable_code)]
-                    match self {
-                        #response
-                    }
+                    #crate_prefix internal::error_response(self.error_info(), self)
                 }
 
             }
@@ -522,9 +1127,35 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
             #[automatically_derived]
             impl #crate_prefix responses::DocumentedResponse for #ident {
 
-                fn openapi(mut responses: #crate_prefix utoipa::openapi::ResponsesBuilder, _schemas: &mut std::collections::HashMap<String, #crate_prefix utoipa::openapi::RefOr<#crate_prefix utoipa::openapi::schema::Schema>>) -> #crate_prefix utoipa::openapi::ResponsesBuilder {
+                #[allow(unused_variables)]
+                fn openapi(responses: #crate_prefix utoipa::openapi::ResponsesBuilder, schemas: &mut std::collections::HashMap<String, #crate_prefix utoipa::openapi::RefOr<#crate_prefix utoipa::openapi::schema::Schema>>) -> #crate_prefix utoipa::openapi::ResponsesBuilder {
+                    let mut errors = Vec::new();
                     #openapi
-                    responses
+                    #crate_prefix internal::document_errors(responses, errors)
+                }
+
+            }
+
+            #[automatically_derived]
+            impl #crate_prefix internal::ErrorPayload for #ident {
+
+                #[allow(unused_variables)]
+                fn from_payload(id: &str, details: #crate_prefix serde_json::Value) -> Option<Result<Self, #crate_prefix serde_json::Error>> {
+                    use #ident::*;
+                    match id {
+                        #payload
+                        _ => None,
+                    }
+                }
+
+                fn unknown(
+                    status: #crate_prefix http::StatusCode,
+                    id: Option<String>,
+                    msg: Option<String>,
+                    body: String,
+                    retry_after: Option<std::time::Duration>,
+                ) -> Self {
+                    Self::Unknown { status, id, msg, body, retry_after, cause: #none }
                 }
 
             }
@@ -535,21 +1166,88 @@ pub fn error0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<Tok
                 type Error = Self;
 
                 fn error_values() -> &'static [#crate_prefix error::ZelusErrorValue<Self::Error>] {
-                    &[ #values_impl ]
+                    const VALUES: &[#crate_prefix error::ZelusErrorValue<#ident>] = &[ #values_impl ];
+                    VALUES
                 }
 
             }
 
             #[automatically_derived]
-            impl From<#crate_prefix reqwest::Error> for #ident {
+            impl #crate_prefix error::ZelusErrorConvert for #ident {
 
-                fn from(err: #crate_prefix reqwest::Error) -> Self {
-                    Self::Communication(err)
+                fn into_parts(mut self) -> (#crate_prefix error::ErrorParts, Option<#crate_prefix error::ErrorSource>) {
+                    use #ident::*;
+                    let source = self.cause_mut().take();
+                    let parts = match self {
+                        Communication(err, _cause) => #crate_prefix error::ErrorParts::Communication(err),
+                        #parts
+                        Unknown { status, id, msg, body, retry_after, cause: _ } => #crate_prefix error::ErrorParts::Unknown { status, id, msg, body, retry_after },
+                        Foreign(info, _cause) => #crate_prefix error::ErrorParts::Foreign(Box::new(info)),
+                    };
+                    (parts, source)
+                }
+
+                fn from_parts(
+                    parts: #crate_prefix error::ErrorParts,
+                    source: Option<#crate_prefix error::ErrorSource>,
+                ) -> Result<Self, (#crate_prefix error::ErrorParts, Option<#crate_prefix error::ErrorSource>)> {
+                    use #ident::*;
+                    let error = match parts {
+                        #communication_parts
+                        #from_parts
+                        #upstream_parts
+                        #crate_prefix error::ErrorParts::Unknown { status, id, msg, body, retry_after } => Unknown { status, id, msg, body, retry_after, cause: #none },
+                        #crate_prefix error::ErrorParts::Foreign(info) => Foreign(*info, #none),
+                        #[allow(unreachable_patterns)]
+                        parts => return Err((parts, source)),
+                    };
+                    Ok(match source {
+                        Some(source) => error.with_source(source),
+                        None => error,
+                    })
+                }
+
+                fn foreign(info: #crate_prefix error::ErrorInfo, error: #crate_prefix error::ErrorSource) -> Self {
+                    Self::Foreign(info, #crate_prefix error::ErrorCause::new(error))
+                }
+
+                fn info(&self) -> #crate_prefix error::ErrorInfo {
+                    self.error_info()
                 }
 
             }
 
+            #communication_from
+
             #impls
         }
     })
 }
+
+/// `ZelusErrorFrom` picks the variant by reference so `From` can keep the error as the cause.
+fn source_impls(
+    crate_prefix: &TokenStream,
+    ident: &Ident,
+    source: &TokenStream,
+    body: &TokenStream,
+) -> TokenStream {
+    quote! {
+        #[automatically_derived]
+        impl #crate_prefix error::ZelusErrorFrom<#source> for #ident {
+
+            fn variant_of(err: &#source) -> Self {
+                #body
+            }
+
+        }
+
+        #[automatically_derived]
+        impl From<#source> for #ident {
+
+            fn from(err: #source) -> Self {
+                <Self as #crate_prefix error::ZelusErrorFrom<#source>>::variant_of(&err).with_source(err)
+            }
+
+        }
+    }
+}
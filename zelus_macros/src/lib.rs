@@ -13,6 +13,8 @@ use manyhow::Emitter;
 #[cfg(feature = "error")]
 mod error;
 #[cfg(feature = "service")]
+mod response;
+#[cfg(feature = "service")]
 mod service;
 #[cfg(any(feature = "error", feature = "service"))]
 mod utils;
@@ -29,6 +31,20 @@ pub fn service(
     service::service0(emitter, &utils::crate_prefix(), &args, input)
 }
 
+#[cfg(feature = "service")]
+#[manyhow::manyhow]
+#[proc_macro_derive(ResponseHeaders)]
+pub fn response_headers(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    response::headers::response_headers0(&utils::crate_prefix(), input)
+}
+
+#[cfg(feature = "service")]
+#[manyhow::manyhow]
+#[proc_macro_derive(ZelusResponse, attributes(status))]
+pub fn zelus_response(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    response::variants::zelus_response0(&utils::crate_prefix(), input)
+}
+
 #[cfg(feature = "error")]
 #[manyhow::manyhow]
 #[proc_macro]
@@ -37,10 +53,8 @@ pub fn error(attr: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenSt
 }
 
 #[cfg(feature = "error")]
+#[manyhow::manyhow]
 #[proc_macro]
-pub fn define_error(attr: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    proc_macro::TokenStream::from(error::define::define_error0(
-        &utils::crate_prefix(),
-        proc_macro2::TokenStream::from(attr),
-    ))
+pub fn define_error(attr: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    error::define::define_error0(&utils::crate_prefix(), attr)
 }
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+pub fn response_headers0(
+    crate_prefix: &TokenStream,
+    input: TokenStream,
+) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = syn::parse2(input)?;
+
+    let Data::Struct(data) = data else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "ResponseHeaders can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = data.fields else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "ResponseHeaders can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut insert = TokenStream::new();
+    let mut extract = TokenStream::new();
+    let mut openapi = TokenStream::new();
+
+    for field in fields.named {
+        let Some(field_ident) = field.ident else {
+            continue;
+        };
+        let field_type = field.ty;
+
+        insert.extend(quote! {
+            <#field_type as #crate_prefix responses::ResponseHeaders>::insert(self.#field_ident, map);
+        });
+        extract.extend(quote! {
+            #field_ident: <#field_type as #crate_prefix responses::ResponseHeaders>::extract(map)?,
+        });
+        openapi.extend(quote! {
+            <#field_type as #crate_prefix responses::ResponseHeaders>::openapi(headers, Some([< variable_header_ #field_ident:snake >]::DESCRIPTION));
+        });
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #crate_prefix paste! {
+            #[automatically_derived]
+            impl #impl_generics #crate_prefix responses::ResponseHeaders for #ident #type_generics #where_clause {
+
+                fn insert(self, map: &mut #crate_prefix http::HeaderMap) {
+                    #insert
+                }
+
+                fn extract(map: &#crate_prefix http::HeaderMap) -> Result<Self, #crate_prefix sdk::ZelusClientError> {
+                    Ok(Self {
+                        #extract
+                    })
+                }
+
+                fn openapi(
+                    headers: &mut std::collections::BTreeMap<String, #crate_prefix utoipa::openapi::Header>,
+                    _description: Option<&'static str>,
+                ) {
+                    #openapi
+                }
+
+            }
+        }
+    })
+}
@@ -0,0 +1,3 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+pub mod headers;
+pub mod variants;
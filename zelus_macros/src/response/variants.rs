@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use std::collections::HashSet;
+use syn::{Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, Meta};
+
+pub fn zelus_response0(crate_prefix: &TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = syn::parse2(input)?;
+
+    let Data::Enum(data) = data else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "ZelusResponse can only be derived for enums",
+        ));
+    };
+
+    let mut into_response = TokenStream::new();
+    let mut from_response = TokenStream::new();
+    let mut openapi = TokenStream::new();
+    let mut statuses = HashSet::new();
+    let mut redirect = quote! { false };
+    let mut has_body = TokenStream::new();
+
+    for variant in data.variants {
+        let variant_ident = variant.ident;
+
+        let mut status = None;
+        let mut doc = Vec::new();
+        for attr in &variant.attrs {
+            if attr.path().is_ident("status") {
+                status = Some(attr.parse_args::<Ident>()?);
+            } else if let Meta::NameValue(name_value) = &attr.meta
+                && name_value.path.is_ident("doc")
+                && let Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) = &name_value.value
+            {
+                doc.push(lit.value().trim().to_owned());
+            } else {
+                // Other attributes are not relevant for the response
+            }
+        }
+        let Some(status) = status else {
+            return Err(syn::Error::new(
+                variant_ident.span(),
+                "Expected #[status(...)] attribute on response variant",
+            ));
+        };
+        if !statuses.insert(status.to_string()) {
+            return Err(syn::Error::new(
+                status.span(),
+                format!("There is already a response variant with the status {status}"),
+            ));
+        }
+
+        redirect.extend(quote! {
+            || matches!(#crate_prefix http::StatusCode::#status.as_u16(), 300..=399)
+        });
+
+        let description = if doc.is_empty() {
+            quote! { None }
+        } else {
+            let doc = LitStr::new(&doc.join("\n"), variant_ident.span());
+            quote! { Some(#doc) }
+        };
+
+        match variant.fields {
+            Fields::Unit => {
+                into_response.extend(quote! {
+                    Self::#variant_ident => #crate_prefix axum::response::IntoResponse::into_response(#crate_prefix http::StatusCode::#status),
+                });
+                from_response.extend(quote! {
+                    if status == #crate_prefix http::StatusCode::#status {
+                        return Ok(Self::#variant_ident);
+                    }
+                });
+                openapi.extend(quote! {
+                    responses = #crate_prefix internal::document_status(
+                        responses,
+                        #crate_prefix utoipa::openapi::ResponsesBuilder::new().response(
+                            #crate_prefix http::StatusCode::#status.as_str(),
+                            #crate_prefix utoipa::openapi::ResponseBuilder::new()
+                                .description(#crate_prefix http::StatusCode::#status.canonical_reason().unwrap_or_default())
+                                .build(),
+                        ),
+                        #crate_prefix http::StatusCode::#status,
+                        #description,
+                    );
+                });
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field_type = fields.unnamed.into_iter().map(|field| field.ty);
+                let field_type = quote! { #(#field_type)* };
+                has_body.extend(quote! {
+                    || <#field_type as #crate_prefix internal::IntoFrameworkResponse>::has_body()
+                });
+                into_response.extend(quote! {
+                    Self::#variant_ident(value) => {
+                        let mut response = <#field_type as #crate_prefix internal::IntoFrameworkResponse>::into_framework_response(value, format);
+                        // Failing to serialize the payload answers with an error status, which is kept
+                        if response.status().is_success() || response.status().is_redirection() {
+                            *response.status_mut() = #crate_prefix http::StatusCode::#status;
+                        }
+                        response
+                    }
+                });
+                from_response.extend(quote! {
+                    if status == #crate_prefix http::StatusCode::#status {
+                        return Ok(Self::#variant_ident(<#field_type as #crate_prefix internal::FromFrameworkResponse>::from_framework_response(response).await?));
+                    }
+                });
+                openapi.extend(quote! {
+                    responses = #crate_prefix internal::document_status(
+                        responses,
+                        <#field_type as #crate_prefix responses::DocumentedResultResponse>::openapi(#crate_prefix utoipa::openapi::ResponsesBuilder::new(), schemas),
+                        #crate_prefix http::StatusCode::#status,
+                        #description,
+                    );
+                });
+            }
+            Fields::Named(_) | Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    variant_ident.span(),
+                    "Response variants must either be unit variants or have exactly one unnamed field",
+                ));
+            }
+        }
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_prefix internal::IntoFrameworkResponse for #ident #type_generics #where_clause {
+
+            fn into_framework_response(self, format: #crate_prefix codec::BodyFormat) -> #crate_prefix axum::response::Response {
+                match self {
+                    #into_response
+                }
+            }
+
+            fn has_body() -> bool {
+                false #has_body
+            }
+
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_prefix axum::response::IntoResponse for #ident #type_generics #where_clause {
+
+            fn into_response(self) -> #crate_prefix axum::response::Response {
+                #crate_prefix internal::IntoFrameworkResponse::into_framework_response(self, #crate_prefix codec::BodyFormat::default())
+            }
+
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_prefix internal::FromFrameworkResponse for #ident #type_generics #where_clause {
+
+            const REDIRECT: bool = #redirect;
+
+            async fn from_framework_response(response: #crate_prefix reqwest::Response) -> Result<Self, #crate_prefix sdk::ZelusClientError> {
+                let status = response.status();
+                #from_response
+                Err(#crate_prefix sdk::ZelusClientError::Status(status))
+            }
+
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_prefix responses::DocumentedResultResponse for #ident #type_generics #where_clause {
+
+            fn openapi(mut responses: #crate_prefix utoipa::openapi::ResponsesBuilder, schemas: &mut std::collections::HashMap<String, #crate_prefix utoipa::openapi::RefOr<#crate_prefix utoipa::openapi::schema::Schema>>) -> #crate_prefix utoipa::openapi::ResponsesBuilder {
+                #openapi
+                responses
+            }
+
+        }
+    })
+}
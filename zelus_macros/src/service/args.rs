@@ -7,6 +7,19 @@ pub struct ServiceArgs {
     pub path: String,
     pub tag: Option<LitStr>,
     pub no_sdk: bool,
+    /// Compresses the responses of the routes, see `ZelusRouter::compression`.
+    pub compression: bool,
+    /// Converts the errors of the routes into each other, see `convert_errors!`.
+    pub convert_errors: bool,
+}
+
+/// One option of `#[service(..)]`.
+enum ServiceOption {
+    Path(String),
+    Tag(LitStr),
+    NoSdk,
+    Compression,
+    ConvertErrors,
 }
 
 impl Parse for ServiceArgs {
@@ -14,17 +27,15 @@ impl Parse for ServiceArgs {
         let mut path = None;
         let mut tag = None;
         let mut no_sdk = false;
-        for (path_opt, tag_opt, no_sdk_opt) in
-            input.parse_terminated(parse_service_args_option, Token![,])?
-        {
-            if let Some(opt) = path_opt {
-                path = Some(opt);
-            }
-            if let Some(opt) = tag_opt {
-                tag = Some(opt);
-            }
-            if no_sdk_opt {
-                no_sdk = true;
+        let mut compression = false;
+        let mut convert_errors = false;
+        for opt in input.parse_terminated(parse_service_args_option, Token![,])? {
+            match opt {
+                ServiceOption::Path(opt) => path = Some(opt),
+                ServiceOption::Tag(opt) => tag = Some(opt),
+                ServiceOption::NoSdk => no_sdk = true,
+                ServiceOption::Compression => compression = true,
+                ServiceOption::ConvertErrors => convert_errors = true,
             }
         }
 
@@ -32,18 +43,19 @@ impl Parse for ServiceArgs {
             path: path.unwrap_or_default(),
             tag,
             no_sdk,
+            compression,
+            convert_errors,
         })
     }
 }
 
-fn parse_service_args_option(
-    stream: ParseStream,
-) -> Result<(Option<String>, Option<LitStr>, bool), syn::Error> {
+fn parse_service_args_option(stream: ParseStream) -> Result<ServiceOption, syn::Error> {
     let opt: Ident = stream.parse()?;
 
-    #[expect(clippy::single_match, reason = "maybe more options later")]
     match opt.to_string().as_str() {
-        "no_sdk" => return Ok((None, None, true)),
+        "no_sdk" => return Ok(ServiceOption::NoSdk),
+        "compression" => return Ok(ServiceOption::Compression),
+        "convert_errors" => return Ok(ServiceOption::ConvertErrors),
         _ => {}
     }
     if stream.peek(Token![=]) {
@@ -51,12 +63,12 @@ fn parse_service_args_option(
         match opt.to_string().as_str() {
             "path" => {
                 let path_literal: LitStr = stream.parse()?;
-                Ok((Some(path_literal.value()), None, false))
+                Ok(ServiceOption::Path(path_literal.value()))
             }
             "tag" => {
                 let tag_literal: LitStr = stream.parse()?;
 
-                Ok((None, Some(tag_literal), false))
+                Ok(ServiceOption::Tag(tag_literal))
             }
             _ => Err(stream.error("Unknown option")),
         }
@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 use crate::service::process::HttpMethod;
 use crate::service::route::RouteArgs;
+#[cfg(feature = "error")]
+use convert_case::{Case, Casing as _};
+#[cfg(feature = "error")]
+use either::Either;
 use manyhow::{Emitter, ErrorMessage};
 use proc_macro2::{Delimiter, Ident, Literal, TokenStream, TokenTree};
 use quote::quote;
@@ -12,6 +16,10 @@ pub struct MacroProcessResult {
     pub route_args: RouteArgs,
     pub example: Option<LitStr>,
     pub doc: Vec<Literal>,
+    /// Ids and status codes of the errors defined inline with `{ Name "msg" STATUS }`.
+    pub custom_errors: Vec<(String, Ident)>,
+    /// Whether the route has its own error, generated by `#[error(..)]`.
+    pub generated_error: bool,
 }
 
 pub fn process(
@@ -29,6 +37,14 @@ pub fn process(
     let mut pre_fn_result1 = Vec::new();
     let mut description = Vec::new();
     let mut example = None;
+    #[cfg(feature = "error")]
+    let mut custom_errors = Vec::new();
+    #[cfg(not(feature = "error"))]
+    let custom_errors = Vec::new();
+    #[cfg(feature = "error")]
+    let mut generated_error = false;
+    #[cfg(not(feature = "error"))]
+    let generated_error = false;
 
     let result_edited = if result.is_empty() {
         post_fn.extend(quote! { -> });
@@ -192,9 +208,21 @@ pub fn process(
                     args.extend(quote! { , });
                     args.extend(group.stream());
                 }
+                // Invalid arguments are reported by `error!`
+                if let Ok(content) =
+                    syn::parse2::<crate::error::ErrorContent>(quote! { #fn_ident #args })
+                {
+                    for (error, _attributes) in content.errors {
+                        if let Either::Left((error_ident, statuscode, _msg)) = error {
+                            custom_errors
+                                .push((error_ident.to_string().to_case(Case::Snake), statuscode));
+                        }
+                    }
+                }
                 errors.extend(quote! {
                         #crate_prefix error::error!([< #fn_ident:camel Error >] #args);
                 });
+                generated_error = true;
 
                 *result = result
                     .clone()
@@ -228,5 +256,7 @@ pub fn process(
         route_args,
         example,
         doc: description,
+        custom_errors,
+        generated_error,
     })
 }
@@ -14,6 +14,7 @@ use crate::service::route::RouteArgs;
 use crate::service::utils::{
     TokenStreamArray, attribute_handle, parse_function_argument, type_option,
 };
+use crate::utils::warning;
 use core::fmt::{Display, Formatter};
 use core::str::FromStr;
 use either::Either;
@@ -100,6 +101,7 @@ impl HttpMethod {
 
 pub struct ProcessedFunction {
     client_def: TokenStream,
+    url_def: TokenStream,
     client_impl: TokenStream,
 
     fn_ident: Ident,
@@ -107,10 +109,14 @@ pub struct ProcessedFunction {
     operations: TokenStream,
     path: String,
     result: TokenStream,
+    documented: TokenStream,
     schema_extra: TokenStream,
     routes_selection: Vec<Ident>,
+    no_auth: bool,
     fn_args_impl: Group,
     function_impl: Option<Group>,
+    custom_errors: Vec<(String, Ident)>,
+    generated_error: bool,
 }
 
 pub fn process(
@@ -149,10 +155,13 @@ pub fn process(
                 query,
                 routes: routes_selection,
                 raw,
-                ..
+                no_auth,
+                status,
             },
         example,
         doc,
+        custom_errors,
+        generated_error,
     } = macros::process(
         emitter,
         crate_prefix,
@@ -336,8 +345,8 @@ pub fn process(
     let mut schema_extra = TokenStream::new();
 
     let mut client_impl_body = TokenStream::new();
-    let mut client_def_body = TokenStream::new();
-    let mut fn_def_args = quote! { &self, };
+    let mut url_def_body = TokenStream::new();
+    let mut fn_def_args = TokenStream::new();
     let mut fn_def_call = TokenStream::new();
 
     path::process(
@@ -349,7 +358,7 @@ pub fn process(
         &mut func_args,
         &mut operations,
         &mut schema_extra,
-        &mut client_def_body,
+        &mut url_def_body,
         &mut fn_def_args,
         &mut fn_def_call,
     )?;
@@ -363,7 +372,7 @@ pub fn process(
         &mut func_args,
         &mut operations,
         &mut schema_extra,
-        &mut client_def_body,
+        &mut url_def_body,
         &mut fn_def_args,
         &mut fn_def_call,
     );
@@ -402,10 +411,22 @@ pub fn process(
         &mut client_impl_body,
     )?;
 
+    let status =
+        status.map(|status| quote! { { #crate_prefix http::StatusCode::#status.as_u16() } });
+    if status.is_some() && (raw || method == HttpMethod::Ws) {
+        emitter.emit(ErrorMessage::new(
+            fn_ident.span(),
+            "The status option can't be used with raw or websocket routes",
+        ));
+        return Err(());
+    }
+
     route::process(
         crate_prefix,
         method,
         raw,
+        status.as_ref(),
+        &TokenStream::from_iter(result.clone()),
         trait_ident,
         &fn_ident,
         func_args,
@@ -428,9 +449,14 @@ pub fn process(
 
     let post_fn = TokenStream::from_iter(post_fn);
     let result = TokenStream::from_iter(result);
-    let fn_def_args = Group::new(Delimiter::Parenthesis, fn_def_args);
+    let url_def_args = Group::new(
+        Delimiter::Parenthesis,
+        quote! { base: &#crate_prefix url::Url, #fn_def_args },
+    );
+    let fn_def_args = Group::new(Delimiter::Parenthesis, quote! { &self, #fn_def_args });
     let fn_args = TokenTree::Group(fn_args);
 
+    let fn_def_call_inner = fn_def_call.clone();
     let fn_def_call = Group::new(Delimiter::Parenthesis, fn_def_call);
     let method_http = method.to_http();
     if method == HttpMethod::Ws {
@@ -442,7 +468,8 @@ pub fn process(
             #client_impl_body
             let response = request
                 .send()
-                .await?;
+                .await
+                .map_err(#crate_prefix sdk::ZelusClientError::Reqwest)?;
             match response.error_for_status_ref() {
                 Ok(_) => {
                     Ok(#crate_prefix internal::from_raw(response))
@@ -454,15 +481,21 @@ pub fn process(
         };
     } else {
         client_impl_body = quote! {
-            let mut request = self.client().request(#crate_prefix http::Method::#method_http, self.[< #fn_ident _url >] #fn_def_call)
-                .header(#crate_prefix http::header::ACCEPT, "application/json");
+            let client = if <<#result as #crate_prefix internal::RouteResult>::Success as #crate_prefix internal::FromFrameworkResponse>::REDIRECT {
+                self.redirect_client()
+            } else {
+                self.client()
+            };
+            let mut request = client.request(#crate_prefix http::Method::#method_http, self.[< #fn_ident _url >] #fn_def_call)
+                .header(#crate_prefix http::header::ACCEPT, self.body_format().media_type());
             #client_impl_body
             let response = request
                 .send()
-                .await?;
+                .await
+                .map_err(#crate_prefix sdk::ZelusClientError::Reqwest)?;
             match response.error_for_status_ref() {
                 Ok(_) => {
-                    Ok(#crate_prefix internal::FrameworkJsonResponse::from_reqwest(response)
+                    Ok(#crate_prefix internal::FrameworkResponse::from_reqwest(response)
                         .await?.0)
                 },
                 Err(_err) => {
@@ -476,27 +509,46 @@ pub fn process(
         #client_impl_body
     } };
 
+    let url_def = quote! {
+
+        #[must_use]
+        pub #fn_keyword #fn_ident #url_def_args -> #crate_prefix url::Url {
+            #url_def_body
+            zelus_result_url
+        }
+
+    };
+
     let client_def = quote! {
 
         #fn_keyword [< #fn_ident _url >] #fn_def_args -> #crate_prefix url::Url {
-            #client_def_body
-            zelus_result_url
+            [< #trait_ident:snake _url >]::#fn_ident(<Self as #crate_prefix sdk::ZelusClientImpl>::base_url(self), #fn_def_call_inner)
         }
 
     };
 
+    let documented = status.as_ref().map_or_else(
+        || result.clone(),
+        |status| quote! { <#result as #crate_prefix internal::RouteStatus<#status>>::Output },
+    );
+
     functions.push(ProcessedFunction {
         client_def,
+        url_def,
         client_impl,
         fn_ident,
         method,
         operations,
         path,
+        documented,
         result,
         schema_extra,
         routes_selection,
+        no_auth,
         fn_args_impl,
         function_impl: end.right(),
+        custom_errors,
+        generated_error,
     });
 
     Ok(func)
@@ -513,8 +565,47 @@ pub fn finish(
 ) {
     let mut routes = HashMap::new();
 
+    // The ids of errors are shared by the routes, e.g. in the error catalog
+    let mut custom_errors = HashMap::new();
+    for function in &functions {
+        for (id, statuscode) in &function.custom_errors {
+            let (other_fn, other_statuscode): &(Ident, Ident) = custom_errors
+                .entry(id.clone())
+                .or_insert_with(|| (function.fn_ident.clone(), statuscode.clone()));
+            if other_statuscode != statuscode {
+                output_extra.extend(warning(
+                    statuscode.span(),
+                    &format!(
+                        "The error {id} is also used with the status {other_statuscode} by {other_fn}"
+                    ),
+                ));
+            }
+        }
+    }
+
+    // With `convert_errors`, `?` works across the errors of the routes
+    let generated_errors = functions
+        .iter()
+        .filter(|function| function.generated_error)
+        .map(|function| &function.fn_ident)
+        .collect::<Vec<_>>();
+    if args.convert_errors && generated_errors.len() > 1 {
+        for from in &generated_errors {
+            let into = generated_errors.iter().filter(|into| *into != from);
+            output_extra.extend(quote! {
+                #crate_prefix paste! {
+                    #crate_prefix convert_errors!(
+                        [< #trait_ident:snake _error >]::[< #from:camel Error >] =>
+                        #( [< #trait_ident:snake _error >]::[< #into:camel Error >] ),*
+                    );
+                }
+            });
+        }
+    }
+
     let mut client_impl_merged = TokenStream::new();
     let mut client_def_merged = TokenStream::new();
+    let mut url_def_merged = TokenStream::new();
     let mut impl_tokens = TokenStream::new();
 
     for ProcessedFunction {
@@ -522,11 +613,14 @@ pub fn finish(
         path,
         method,
         routes_selection,
+        no_auth,
         result,
+        documented,
         operations,
         schema_extra,
         client_impl,
         client_def,
+        url_def,
         fn_args_impl,
         function_impl,
         ..
@@ -534,6 +628,7 @@ pub fn finish(
     {
         client_impl_merged.extend(client_impl);
         client_def_merged.extend(client_def);
+        url_def_merged.extend(url_def);
 
         let method = method.to_utoipa();
 
@@ -541,16 +636,25 @@ pub fn finish(
 
         let operation_id = LitStr::new(&fn_ident.to_string(), Span::call_site());
 
+        let route = Ident::new(
+            if no_auth {
+                "route"
+            } else {
+                "authenticated_route"
+            },
+            Span::call_site(),
+        );
+
         let token = quote! {
 
-            .route(
+            .#route(
                 #path,
                 #crate_prefix utoipa::openapi::HttpMethod::#method,
                 {
                     let mut schemas = std::collections::HashMap::new();
                     #schema_extra
 
-                    let responses = <#result as #crate_prefix responses::DocumentedResponse>::openapi(
+                    let responses = <#documented as #crate_prefix responses::DocumentedResponse>::openapi(
                         #crate_prefix utoipa::openapi::ResponsesBuilder::new(),
                         &mut schemas
                     );
@@ -596,6 +700,12 @@ pub fn finish(
         )
     });
 
+    let compression = if args.compression {
+        quote! { .compression() }
+    } else {
+        TokenStream::new()
+    };
+
     for (selection, routes) in routes.into_values() {
         trait_body_output.extend(quote! {
 
@@ -610,7 +720,7 @@ pub fn finish(
                     let mut router = ZelusRouter::new();
                     let _ = (&mut router)
                         #routes;
-                    router
+                    router #compression
                 }
             }
         });
@@ -629,6 +739,20 @@ pub fn finish(
         });
     }
 
+    output_extra.extend(quote! {
+
+        #crate_prefix paste! {
+
+            pub mod [< #trait_ident:snake _url >] {
+                use super::*;
+
+                #url_def_merged
+            }
+
+        }
+
+    });
+
     if !args.no_sdk {
         output_extra.extend(quote! {
 
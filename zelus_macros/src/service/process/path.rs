@@ -14,7 +14,7 @@ pub fn process(
     func_args: &mut Vec<TokenStream>,
     operations: &mut TokenStream,
     schema_extra: &mut TokenStream,
-    client_def_body: &mut TokenStream,
+    url_def_body: &mut TokenStream,
     fn_def_args: &mut TokenStream,
     fn_def_call: &mut TokenStream,
 ) -> Result<(), ()> {
@@ -98,11 +98,11 @@ pub fn process(
     };
 
     let path = LitStr::new(path, Span::call_site());
-    client_def_body.extend(quote! {
+    url_def_body.extend(quote! {
         use #crate_prefix serde::Serialize;
         #urlencode
         let zelus_result_url: String = format!(#path);
-        let mut zelus_result_url = <Self as #crate_prefix sdk::ZelusClientImpl>::base_url(self).join(&zelus_result_url).expect("Expected url");
+        let mut zelus_result_url = base.join(&zelus_result_url).expect("Expected url");
     });
     Ok(())
 }
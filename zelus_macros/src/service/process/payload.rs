@@ -75,29 +75,26 @@ pub fn process(
             });
         } else {
             http_args.extend(quote! {
-                #crate_prefix axum::extract::Json(
+                #crate_prefix internal::Payload(
                     #arg_name
                 ):
-                #crate_prefix axum::extract::Json<
+                #crate_prefix internal::Payload<
                     #arg_type
                 >,
             });
-            func_args.push(quote! { #crate_prefix axum::extract::Json< #arg_type > });
+            func_args.push(quote! { #crate_prefix internal::Payload< #arg_type > });
 
             operations.extend(quote! {
                 operations = operations.request_body(Some(
-                    #crate_prefix utoipa::openapi::request_body::RequestBodyBuilder::new()
-                        .content("application/json", #crate_prefix utoipa::openapi::content::Content::builder()
-                            .schema(Some(
-                                #crate_prefix utoipa::openapi::schema::RefBuilder::new()
-                                    .ref_location_from_schema_name(< #arg_type as #crate_prefix utoipa::ToSchema >::name())
-                                    .build()
-                            ))
-                            #content_extra
-                            .build()
-                        )
-                        .required(Some(#crate_prefix utoipa::openapi::Required::True))
+                    #crate_prefix internal::payload_request_body(&#crate_prefix utoipa::openapi::content::Content::builder()
+                        .schema(Some(
+                            #crate_prefix utoipa::openapi::schema::RefBuilder::new()
+                                .ref_location_from_schema_name(< #arg_type as #crate_prefix utoipa::ToSchema >::name())
+                                .build()
+                        ))
+                        #content_extra
                         .build()
+                    )
                 ));
             });
             schema_extra.extend(quote! {
@@ -108,7 +105,7 @@ pub fn process(
             });
 
             client_impl_body.extend(quote! {
-                request = request.json(&#arg_name);
+                request = #crate_prefix internal::payload(request, self.body_format(), &#arg_name)?;
             });
         }
     } else if !fn_args_payload.is_empty() {
@@ -138,30 +135,26 @@ pub fn process(
             }
         });
         http_args.extend(quote! {
-                #crate_prefix axum::extract::Json(
+                #crate_prefix internal::Payload(
                     [< #fn_ident:camel Payload >] { #payload_names }
                 ):
-                #crate_prefix axum::extract::Json<
+                #crate_prefix internal::Payload<
                     [< #fn_ident:camel Payload >]
                 >,
         });
-        func_args
-            .push(quote! { #crate_prefix axum::extract::Json< [< #fn_ident:camel Payload >] > });
+        func_args.push(quote! { #crate_prefix internal::Payload< [< #fn_ident:camel Payload >] > });
 
         operations.extend(quote! {
             operations = operations.request_body(Some(
-                #crate_prefix utoipa::openapi::request_body::RequestBodyBuilder::new()
-                    .content("application/json", #crate_prefix utoipa::openapi::content::Content::builder()
-                        .schema(Some(
-                            #crate_prefix utoipa::openapi::schema::RefBuilder::new()
-                                .ref_location_from_schema_name(< [< __ #trait_ident:snake _zelus_routes >]::[< #fn_ident:camel Payload >] as #crate_prefix utoipa::ToSchema >::name())
-                                .build()
-                        ))
-                        #content_extra
-                        .build()
-                    )
-                    .required(Some(#crate_prefix utoipa::openapi::Required::True))
+                #crate_prefix internal::payload_request_body(&#crate_prefix utoipa::openapi::content::Content::builder()
+                    .schema(Some(
+                        #crate_prefix utoipa::openapi::schema::RefBuilder::new()
+                            .ref_location_from_schema_name(< [< __ #trait_ident:snake _zelus_routes >]::[< #fn_ident:camel Payload >] as #crate_prefix utoipa::ToSchema >::name())
+                            .build()
+                    ))
+                    #content_extra
                     .build()
+                )
             ));
         });
         schema_extra.extend(quote! {
@@ -172,7 +165,7 @@ pub fn process(
         });
 
         client_impl_body.extend(quote! {
-            request = request.json(&[< __ #trait_ident:snake _zelus_routes >]::[< #fn_ident:camel Payload >] { #payload_names });
+            request = #crate_prefix internal::payload(request, self.body_format(), &[< __ #trait_ident:snake _zelus_routes >]::[< #fn_ident:camel Payload >] { #payload_names })?;
         });
     } else {
         // If there are no payload arguments, there is no payload.
@@ -13,7 +13,7 @@ pub fn process(
     func_args: &mut Vec<TokenStream>,
     operations: &mut TokenStream,
     schema_extra: &mut TokenStream,
-    client_def_body: &mut TokenStream,
+    url_def_body: &mut TokenStream,
     fn_def_args: &mut TokenStream,
     fn_def_call: &mut TokenStream,
 ) {
@@ -42,8 +42,8 @@ pub fn process(
         })
         .collect();
     if !fn_args_query.is_empty() {
-        let mut client_def_body_pre = TokenStream::new();
-        let mut client_def_body_post = quote! {
+        let mut url_def_body_pre = TokenStream::new();
+        let mut url_def_body_post = quote! {
             zelus_result_url.query_pairs_mut()
         };
 
@@ -98,18 +98,18 @@ pub fn process(
             fn_def_args.extend(quote! { #arg_name: #arg_type, });
             fn_def_call.extend(quote! { #arg_name, });
 
-            client_def_body_pre.extend(quote! {
+            url_def_body_pre.extend(quote! {
                 let #arg_name: Option<String> = #arg_name.serialize(#crate_prefix internal::StringSerializer).unwrap();
             });
-            client_def_body_post.extend(quote! {
+            url_def_body_post.extend(quote! {
                 .extend_pairs(#arg_name.map(|val| (#variable_literal, val)).iter())
             });
         }
 
-        client_def_body_post.extend(quote! { ; });
+        url_def_body_post.extend(quote! { ; });
 
-        client_def_body.extend(client_def_body_pre);
-        client_def_body.extend(client_def_body_post);
+        url_def_body.extend(url_def_body_pre);
+        url_def_body.extend(url_def_body_post);
 
         routes.extend(quote! {
             #[derive(Debug, Clone, #crate_prefix serde::Serialize, #crate_prefix serde::Deserialize)]
@@ -7,6 +7,8 @@ pub fn process(
     crate_prefix: &TokenStream,
     method: HttpMethod,
     raw: bool,
+    status: Option<&TokenStream>,
+    result: &TokenStream,
     trait_ident: &Ident,
     fn_ident: &Ident,
     mut func_args: Vec<TokenStream>,
@@ -27,6 +29,20 @@ pub fn process(
         call_args = quote! { ws, #call_args };
     }
 
+    let result_map = if HttpMethod::Ws == method || raw {
+        TokenStream::new()
+    } else {
+        // Only negotiated for responses with a body
+        let accept = quote! { #crate_prefix internal::Accept<<#result as #crate_prefix internal::RouteResult>::Success> };
+        func_args.insert(0, accept.clone());
+        http_args = quote! { #crate_prefix internal::Accept(zelus_accept, _): #accept, #http_args };
+        let value = status.map_or_else(
+            || quote! { value },
+            |status| quote! { #crate_prefix responses::WithStatus::<#status, _>(value) },
+        );
+        quote! { .map(|value| #crate_prefix internal::FrameworkResponse(#value, zelus_accept)) }
+    };
+
     if func_args.is_empty() {
         target_generic_types.extend(quote! { (), });
     } else {
@@ -43,12 +59,6 @@ pub fn process(
         }
     }
 
-    let result_map = if HttpMethod::Ws == method || raw {
-        TokenStream::new()
-    } else {
-        quote! { .map(#crate_prefix internal::FrameworkJsonResponse) }
-    };
-
     routes.extend(quote! {
 
         pub(crate) fn #fn_ident<T: super::#trait_ident + Clone + Send + Sync + 'static, #generic_types>(service: T) -> impl #crate_prefix axum::handler::Handler<(#target_generic_types), ()> #generic_type_conditions {
@@ -15,6 +15,8 @@ pub struct RouteArgs {
     pub no_auth: bool,
     pub raw: bool,
     pub routes: Vec<Ident>,
+    /// Success status code, like `status = CREATED`.
+    pub status: Option<Ident>,
 }
 
 impl Parse for RouteArgs {
@@ -29,10 +31,11 @@ impl Parse for RouteArgs {
         let mut routes = vec![Ident::new("default", Span::call_site())];
         let mut raw = false;
         let mut no_auth = false;
+        let mut status = None;
 
         if input.peek(Token![,]) {
             let _: Punct = input.parse()?;
-            for (method_opt, query_opt, routes_opt, raw_opt, no_auth_opt) in
+            for (method_opt, query_opt, routes_opt, raw_opt, no_auth_opt, status_opt) in
                 input.parse_terminated(parse_route_args_option, Token![,])?
             {
                 if let Some(opt) = method_opt {
@@ -41,6 +44,9 @@ impl Parse for RouteArgs {
                 if let Some(opt) = routes_opt {
                     routes = opt;
                 }
+                if let Some(opt) = status_opt {
+                    status = Some(opt);
+                }
                 raw |= raw_opt;
                 no_auth |= no_auth_opt;
                 query.extend(query_opt);
@@ -61,6 +67,7 @@ impl Parse for RouteArgs {
             routes,
             raw,
             no_auth,
+            status,
         })
     }
 }
@@ -95,15 +102,16 @@ fn parse_route_args_option(
         Option<Vec<Ident>>,
         bool,
         bool,
+        Option<Ident>,
     ),
     syn::Error,
 > {
     let opt: Ident = stream.parse()?;
     if opt.to_string().eq("raw") {
-        return Ok((None, HashMap::new(), None, true, false));
+        return Ok((None, HashMap::new(), None, true, false, None));
     }
     if opt.to_string().eq("no_auth") {
-        return Ok((None, HashMap::new(), None, false, true));
+        return Ok((None, HashMap::new(), None, false, true, None));
     }
     if stream.peek(Token![=]) {
         let _: Punct = stream.parse()?;
@@ -111,7 +119,7 @@ fn parse_route_args_option(
             "method" => {
                 let method_ident: Ident = stream.parse()?;
                 match HttpMethod::from_str(&method_ident.to_string()) {
-                    Ok(val) => Ok((Some(val), HashMap::new(), None, false, false)),
+                    Ok(val) => Ok((Some(val), HashMap::new(), None, false, false, None)),
                     Err(()) => Err(stream.error("Unknown method")),
                 }
             }
@@ -130,6 +138,7 @@ fn parse_route_args_option(
                     None,
                     false,
                     false,
+                    None,
                 ))
             }
             "routes" => {
@@ -148,8 +157,17 @@ fn parse_route_args_option(
                     ),
                     false,
                     false,
+                    None,
                 ))
             }
+            "status" => Ok((
+                None,
+                HashMap::new(),
+                None,
+                false,
+                false,
+                Some(stream.parse()?),
+            )),
             _ => Err(stream.error("Unknown option")),
         }
     } else {
@@ -1,9 +1,22 @@
 // SPDX-License-Identifier: AGPL-3.0-only
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
 
 pub fn crate_prefix() -> TokenStream {
     quote! {
         ::zelus::
     }
 }
+
+/// Emits a warning at `span`, by using a deprecated item there.
+#[cfg(feature = "service")]
+pub fn warning(span: Span, message: &str) -> TokenStream {
+    let ident = Ident::new("ZelusWarning", span);
+    quote_spanned! { span =>
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct ZelusWarning;
+            let _warning = #ident;
+        };
+    }
+}